@@ -0,0 +1,155 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// The placement of a symbol which is not part of a custom alphabet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnknownSymbol {
+    /// Unknown symbols collate before all known symbols
+    First,
+
+    /// Unknown symbols collate after all known symbols
+    Last,
+}
+
+/// A collator for sequences of symbols, according to a custom alphabet.
+///
+/// Sequences are collated lexicographically, symbol by symbol.
+/// Symbols which are not part of the alphabet are placed according to an [`UnknownSymbol`]
+/// policy and collated relative to each other by their natural order.
+///
+/// Example:
+/// ```
+/// use collate::{AlphabetCollator, CollateRef, UnknownSymbol};
+///
+/// let collator = AlphabetCollator::new("TCGA".chars(), UnknownSymbol::Last);
+///
+/// let mut sequences = vec!["GATTACA", "TAG", "CAT", "TNT"];
+/// sequences.sort_by(|l, r| collator.cmp_ref(*l, *r));
+/// assert_eq!(sequences, ["TAG", "TNT", "CAT", "GATTACA"]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(deserialize = "T: Deserialize<'de> + Eq + Hash"))
+)]
+pub struct AlphabetCollator<T> {
+    ranks: HashMap<T, usize>,
+    unknown: UnknownSymbol,
+}
+
+impl<T: Eq + Hash> AlphabetCollator<T> {
+    /// Construct a new [`AlphabetCollator`] for the given `alphabet`, in collation order.
+    /// If a symbol appears more than once, its first position is used.
+    pub fn new<I: IntoIterator<Item = T>>(alphabet: I, unknown: UnknownSymbol) -> Self {
+        let mut ranks = HashMap::new();
+
+        for symbol in alphabet {
+            let rank = ranks.len();
+            ranks.entry(symbol).or_insert(rank);
+        }
+
+        Self { ranks, unknown }
+    }
+}
+
+impl<T: Ord + Hash> AlphabetCollator<T> {
+    /// Return the collation of the `left` symbol relative to the `right` symbol.
+    pub fn cmp_symbol(&self, left: &T, right: &T) -> Ordering {
+        match (self.ranks.get(left), self.ranks.get(right)) {
+            (Some(l), Some(r)) => l.cmp(r),
+            (None, None) => left.cmp(right),
+            (None, Some(_)) => match self.unknown {
+                UnknownSymbol::First => Ordering::Less,
+                UnknownSymbol::Last => Ordering::Greater,
+            },
+            (Some(_), None) => match self.unknown {
+                UnknownSymbol::First => Ordering::Greater,
+                UnknownSymbol::Last => Ordering::Less,
+            },
+        }
+    }
+
+    fn cmp_symbols<I>(&self, mut left: I, mut right: I) -> Ordering
+    where
+        I: Iterator,
+        I::Item: Borrow<T>,
+    {
+        loop {
+            let order = match (left.next(), right.next()) {
+                (Some(l), Some(r)) => self.cmp_symbol(l.borrow(), r.borrow()),
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+            };
+
+            if order != Ordering::Equal {
+                return order;
+            }
+        }
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for AlphabetCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.unknown == other.unknown && self.ranks == other.ranks
+    }
+}
+
+impl<T: Eq + Hash> Eq for AlphabetCollator<T> {}
+
+impl<T: Ord + Hash> Collate for AlphabetCollator<T> {
+    type Value = Vec<T>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_symbols(left.iter(), right.iter())
+    }
+}
+
+impl<T: Ord + Hash> CollateRef<[T]> for AlphabetCollator<T> {
+    fn cmp_ref(&self, left: &[T], right: &[T]) -> Ordering {
+        self.cmp_symbols(left.iter(), right.iter())
+    }
+}
+
+impl CollateRef<str> for AlphabetCollator<char> {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        self.cmp_symbols(left.chars(), right.chars())
+    }
+}
+
+impl CollateRef<String> for AlphabetCollator<char> {
+    fn cmp_ref(&self, left: &String, right: &String) -> Ordering {
+        self.cmp_ref(left.as_str(), right.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_symbols() {
+        let ranks = [
+            "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+        ];
+
+        let first = AlphabetCollator::new(ranks, UnknownSymbol::First);
+        assert_eq!(first.cmp_ref(&["A"][..], &["2"]), Ordering::Greater);
+        assert_eq!(first.cmp_ref(&["Joker"][..], &["2"]), Ordering::Less);
+        assert_eq!(first.cmp_ref(&["A", "2"][..], &["A"]), Ordering::Greater);
+
+        let last = AlphabetCollator::new(ranks, UnknownSymbol::Last);
+        assert_eq!(last.cmp_ref(&["Joker"][..], &["A"]), Ordering::Greater);
+        assert_eq!(last.cmp_ref(&["Joker"][..], &["Blank"]), Ordering::Greater);
+        assert_eq!(last.cmp(&vec!["K", "Q"], &vec!["K", "Q"]), Ordering::Equal);
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+use crate::CollateEquivalent;
+
+/// Return the index of the first element of the sorted `slice` which does not collate
+/// before `value`, i.e. the leftmost position at which `value` could be inserted.
+///
+/// Example:
+/// ```
+/// use collate::{bisect_left, bisect_right, Collator};
+///
+/// let slice = [1, 2, 2, 3];
+/// assert_eq!(bisect_left(&slice, &2, &Collator::default()), 1);
+/// assert_eq!(bisect_right(&slice, &2, &Collator::default()), 3);
+/// ```
+pub fn bisect_left<T, Q, C>(slice: &[T], value: &Q, collator: &C) -> usize
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+{
+    partition_point_by_collator(slice, value, collator, |order| order == Ordering::Less)
+}
+
+/// Return the index of the first element of the sorted `slice` which collates after `value`,
+/// i.e. the rightmost position at which `value` could be inserted.
+pub fn bisect_right<T, Q, C>(slice: &[T], value: &Q, collator: &C) -> usize
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+{
+    partition_point_by_collator(slice, value, collator, |order| order != Ordering::Greater)
+}
+
+/// Return the half-open interval `(start, end)` of indices of the elements of the sorted `slice`
+/// which lie within the given `range`, so that `&slice[start..end]` are the matching elements.
+///
+/// If the `range` is empty (e.g. its start collates after its end), `start == end`.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{bisect_range, Collator, LexCollator};
+///
+/// // composite keys sorted by a lexicographic collator
+/// let keys = [vec![0, 5], vec![1], vec![1, 2], vec![1, 7], vec![2, 0]];
+/// let collator = LexCollator::new(Collator::<u32>::default());
+///
+/// // every key which begins with 1
+/// let range = (Bound::Included(vec![1]), Bound::Excluded(vec![2]));
+/// assert_eq!(bisect_range(&keys, &range, &collator), (1, 4));
+/// ```
+pub fn bisect_range<T, Q, C, R>(slice: &[T], range: &R, collator: &C) -> (usize, usize)
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+    R: RangeBounds<Q>,
+{
+    bisect_range_by(slice, range, collator, |element| element)
+}
+
+// like `bisect_range`, but collate the value which `project` borrows from each element
+pub(crate) fn bisect_range_by<E, T, Q, C, R, F>(
+    slice: &[E],
+    range: &R,
+    collator: &C,
+    project: F,
+) -> (usize, usize)
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+    R: RangeBounds<Q>,
+    F: Fn(&E) -> &T,
+{
+    let cmp = |element: &E, value: &Q| collator.cmp_equivalent(project(element), value);
+
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(start) => slice.partition_point(|e| cmp(e, start) == Ordering::Less),
+        Bound::Excluded(start) => slice.partition_point(|e| cmp(e, start) != Ordering::Greater),
+    };
+
+    let end = match range.end_bound() {
+        Bound::Unbounded => slice.len(),
+        Bound::Included(end) => slice.partition_point(|e| cmp(e, end) != Ordering::Greater),
+        Bound::Excluded(end) => slice.partition_point(|e| cmp(e, end) == Ordering::Less),
+    };
+
+    (start, end.max(start))
+}
+
+/// Borrow the contiguous subslice of the sorted `slice` whose elements lie within `range`.
+///
+/// Example:
+/// ```
+/// use collate::{slice_range, Collator};
+///
+/// let slice = [1, 3, 5, 7, 9];
+/// let collator = Collator::default();
+/// assert_eq!(slice_range(&slice, &(3..=7), &collator), [3, 5, 7]);
+/// assert_eq!(slice_range(&slice, &(4..5), &collator), [] as [u32; 0]);
+/// ```
+pub fn slice_range<'a, T, Q, C, R>(slice: &'a [T], range: &R, collator: &C) -> &'a [T]
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+    R: RangeBounds<Q>,
+{
+    let (start, end) = bisect_range(slice, range, collator);
+    &slice[start..end]
+}
+
+/// Mutably borrow the contiguous subslice of the sorted `slice` whose elements lie within `range`.
+pub fn slice_range_mut<'a, T, Q, C, R>(slice: &'a mut [T], range: &R, collator: &C) -> &'a mut [T]
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+    R: RangeBounds<Q>,
+{
+    let (start, end) = bisect_range(slice, range, collator);
+    &mut slice[start..end]
+}
+
+/// Search the sorted `slice` for an element which collates equal to `value`,
+/// like [`slice::binary_search`].
+///
+/// Returns `Ok` with the index of a matching element (any one, if there are several),
+/// or `Err` with the index at which `value` could be inserted to keep the `slice` sorted.
+///
+/// Example:
+/// ```
+/// use collate::{binary_search_by_collator, Collator};
+///
+/// let slice = ["a", "c", "e"].map(String::from);
+/// let collator = Collator::<String>::default();
+/// assert_eq!(binary_search_by_collator(&slice, "c", &collator), Ok(1));
+/// assert_eq!(binary_search_by_collator(&slice, "d", &collator), Err(2));
+/// ```
+pub fn binary_search_by_collator<T, Q, C>(
+    slice: &[T],
+    value: &Q,
+    collator: &C,
+) -> Result<usize, usize>
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+{
+    slice.binary_search_by(|element| collator.cmp_equivalent(element, value))
+}
+
+/// Return the index of the first element of the `slice` for which `pred` returns `false`,
+/// given the collation of that element relative to `value`, like [`slice::partition_point`].
+///
+/// The `slice` must be partitioned by `pred`, i.e. `pred` must return `true` for every element
+/// of a prefix of the `slice` and `false` for every element of the remainder.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{partition_point_by_collator, Collator};
+///
+/// let slice = [1, 3, 5, 7];
+/// let index = partition_point_by_collator(&slice, &5, &Collator::default(), |order| {
+///     order == Ordering::Less
+/// });
+///
+/// assert_eq!(index, 2);
+/// ```
+pub fn partition_point_by_collator<T, Q, C, P>(
+    slice: &[T],
+    value: &Q,
+    collator: &C,
+    mut pred: P,
+) -> usize
+where
+    Q: ?Sized,
+    C: CollateEquivalent<T, Q>,
+    P: FnMut(Ordering) -> bool,
+{
+    slice.partition_point(|element| pred(collator.cmp_equivalent(element, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_bisect() {
+        let collator = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let slice = [9, 7, 7, 7, 3];
+
+        assert_eq!(bisect_left(&slice, &7, &collator), 1);
+        assert_eq!(bisect_right(&slice, &7, &collator), 4);
+        assert_eq!(bisect_left(&slice, &10, &collator), 0);
+        assert_eq!(bisect_right(&slice, &0, &collator), 5);
+
+        assert!(matches!(
+            binary_search_by_collator(&slice, &7, &collator),
+            Ok(1..=3)
+        ));
+        assert_eq!(binary_search_by_collator(&slice, &5, &collator), Err(4));
+        assert_eq!(
+            binary_search_by_collator(&[] as &[u32], &5, &collator),
+            Err(0)
+        );
+    }
+
+    #[test]
+    fn test_bisect_range() {
+        let collator = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let slice = [9, 7, 7, 7, 3];
+
+        assert_eq!(bisect_range(&slice, &(7..=7), &collator), (1, 4));
+        assert_eq!(bisect_range(&slice, &(..7), &collator), (0, 1));
+        assert_eq!(bisect_range(&slice, &(7..), &collator), (1, 5));
+        assert_eq!(bisect_range(&slice, &(..), &collator), (0, 5));
+
+        let exclusive = (Bound::Excluded(9), Bound::Excluded(3));
+        assert_eq!(bisect_range(&slice, &exclusive, &collator), (1, 4));
+
+        // an empty range
+        assert_eq!(bisect_range(&slice, &(3..9), &collator), (4, 4));
+
+        let mut slice = slice;
+        assert_eq!(
+            slice_range(&slice, &(Bound::Included(8), Bound::Included(3)), &collator),
+            [7, 7, 7, 3]
+        );
+
+        slice_range_mut(&mut slice, &(7..=7), &collator).fill(6);
+        assert_eq!(slice, [9, 6, 6, 6, 3]);
+    }
+}
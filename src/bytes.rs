@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// The order in which a [`BytesCollator`] collates byte strings.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BytesOrder {
+    /// Collate byte strings lexicographically by byte value, like `memcmp`
+    #[default]
+    Memcmp,
+
+    /// Collate byte strings lexicographically, ignoring the case of ASCII letters
+    AsciiCaseInsensitive,
+
+    /// Collate shorter byte strings first, and byte strings of equal length like `memcmp`
+    LengthFirst,
+}
+
+/// A collator for byte strings, e.g. binary keys in a key-value store.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{BytesCollator, BytesOrder, CollateRef};
+///
+/// let collator = BytesCollator::new(BytesOrder::Memcmp);
+/// assert_eq!(collator.cmp_ref(b"B".as_slice(), b"a".as_slice()), Ordering::Less);
+///
+/// let collator = BytesCollator::new(BytesOrder::AsciiCaseInsensitive);
+/// assert_eq!(collator.cmp_ref(b"KEY\x00".as_slice(), b"key\x00".as_slice()), Ordering::Equal);
+///
+/// let collator = BytesCollator::new(BytesOrder::LengthFirst);
+/// assert_eq!(collator.cmp_ref(b"zz".as_slice(), b"aaa".as_slice()), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BytesCollator {
+    order: BytesOrder,
+}
+
+impl BytesCollator {
+    /// Construct a new [`BytesCollator`] with the given [`BytesOrder`].
+    pub fn new(order: BytesOrder) -> Self {
+        Self { order }
+    }
+
+    /// The [`BytesOrder`] of this collator.
+    pub fn order(&self) -> BytesOrder {
+        self.order
+    }
+}
+
+impl Collate for BytesCollator {
+    type Value = Vec<u8>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_slice(), right.as_slice())
+    }
+}
+
+impl CollateRef<[u8]> for BytesCollator {
+    fn cmp_ref(&self, left: &[u8], right: &[u8]) -> Ordering {
+        match self.order {
+            BytesOrder::Memcmp => left.cmp(right),
+            BytesOrder::AsciiCaseInsensitive => left
+                .iter()
+                .map(u8::to_ascii_lowercase)
+                .cmp(right.iter().map(u8::to_ascii_lowercase)),
+            BytesOrder::LengthFirst => left.len().cmp(&right.len()).then_with(|| left.cmp(right)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_collator() {
+        let values: [&[u8]; 5] = [b"b", b"A", b"ab", b"", b"\xff"];
+
+        let sorted = |order| {
+            let collator = BytesCollator::new(order);
+            let mut values = values.to_vec();
+            values.sort_by(|l, r| collator.cmp_ref(*l, *r));
+            values
+        };
+
+        let expected: [&[u8]; 5] = [b"", b"A", b"ab", b"b", b"\xff"];
+        assert_eq!(sorted(BytesOrder::Memcmp), expected);
+
+        let expected: [&[u8]; 5] = [b"", b"A", b"ab", b"b", b"\xff"];
+        assert_eq!(sorted(BytesOrder::AsciiCaseInsensitive), expected);
+
+        let expected: [&[u8]; 5] = [b"", b"A", b"b", b"\xff", b"ab"];
+        assert_eq!(sorted(BytesOrder::LengthFirst), expected);
+
+        let collator = BytesCollator::new(BytesOrder::AsciiCaseInsensitive);
+        assert_eq!(
+            collator.cmp_ref(b"a".as_slice(), b"B".as_slice()),
+            Ordering::Less
+        );
+        assert_eq!(
+            collator.cmp_ref(b"[".as_slice(), b"a".as_slice()),
+            Ordering::Less
+        );
+        assert_eq!(
+            collator.cmp_ref(b"[".as_slice(), b"A".as_slice()),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use lru::LruCache;
+
+use crate::Collate;
+
+/// A collator which collates values of type `T` by a sort key which is expensive to compute,
+/// for example the sort key bytes of a localized string.
+///
+/// The sort key of each value is computed at most once while it remains in an internal
+/// least-recently-used cache of the given `capacity`, which is useful when a stream combinator
+/// like `merge` or `diff` compares the same pending value many times.
+/// Two [`CachedKeyCollator`]s are equal if their key collators and capacities are equal.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::num::NonZeroUsize;
+/// use collate::{CachedKeyCollator, Collate, Collator};
+///
+/// let capacity = NonZeroUsize::new(64).unwrap();
+/// let collator = CachedKeyCollator::new(Collator::<usize>::default(), capacity, |s: &String| {
+///     s.chars().filter(|c| c.is_alphabetic()).count()
+/// });
+///
+/// assert_eq!(collator.cmp(&"a-b-c".to_string(), &"abcd".to_string()), Ordering::Less);
+/// assert_eq!(collator.len(), 2);
+/// ```
+pub struct CachedKeyCollator<C: Collate, K, T> {
+    collator: C,
+    key: K,
+    cache: Mutex<LruCache<T, C::Value>>,
+}
+
+impl<C: Collate, K, T> CachedKeyCollator<C, K, T>
+where
+    K: Fn(&T) -> C::Value,
+    T: Hash + Eq,
+{
+    /// Construct a new [`CachedKeyCollator`] which collates the sort keys computed by `key`
+    /// using the given `collator`, caching at most `capacity` sort keys.
+    pub fn new(collator: C, capacity: NonZeroUsize, key: K) -> Self {
+        Self {
+            collator,
+            key,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl<C: Collate, K, T: Hash + Eq> CachedKeyCollator<C, K, T> {
+    /// Borrow the collator used to collate sort keys.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+
+    /// The maximum number of sort keys to cache.
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.lock().cap()
+    }
+
+    /// The number of sort keys currently cached.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Return `true` if no sort keys are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Discard all cached sort keys.
+    pub fn clear(&self) {
+        self.lock().clear()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<T, C::Value>> {
+        // the cache is always left in a consistent state, so a poisoned lock is safe to reuse
+        self.cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<C, K, T> Collate for CachedKeyCollator<C, K, T>
+where
+    C: Collate,
+    C::Value: Clone,
+    K: Fn(&T) -> C::Value,
+    T: Hash + Eq + Clone,
+{
+    type Value = T;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        let (left, right) = {
+            let mut cache = self.lock();
+            (
+                self.sort_key(&mut cache, left),
+                self.sort_key(&mut cache, right),
+            )
+        };
+
+        self.collator.cmp(&left, &right)
+    }
+}
+
+impl<C, K, T> CachedKeyCollator<C, K, T>
+where
+    C: Collate,
+    C::Value: Clone,
+    K: Fn(&T) -> C::Value,
+    T: Hash + Eq + Clone,
+{
+    // look up the sort key of `value`, computing and caching it (and cloning `value`) on a miss
+    fn sort_key(&self, cache: &mut LruCache<T, C::Value>, value: &T) -> C::Value {
+        if let Some(key) = cache.get(value) {
+            return key.clone();
+        }
+
+        let key = (self.key)(value);
+        cache.put(value.clone(), key.clone());
+        key
+    }
+}
+
+impl<C: Collate, K, T: Hash + Eq> PartialEq for CachedKeyCollator<C, K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.collator == other.collator && self.capacity() == other.capacity()
+    }
+}
+
+impl<C: Collate, K, T: Hash + Eq> Eq for CachedKeyCollator<C, K, T> {}
+
+impl<C: Collate + fmt::Debug, K, T: Hash + Eq> fmt::Debug for CachedKeyCollator<C, K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedKeyCollator")
+            .field("collator", &self.collator)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_cached_key_collator() {
+        let computed = Cell::new(0);
+        let capacity = NonZeroUsize::new(2).unwrap();
+        let collator =
+            CachedKeyCollator::new(Collator::<String>::default(), capacity, |s: &String| {
+                computed.set(computed.get() + 1);
+                s.to_lowercase()
+            });
+
+        let [a, b, c] = ["B", "a", "C"].map(String::from);
+
+        assert_eq!(collator.cmp(&a, &b), Ordering::Greater);
+        assert_eq!(collator.cmp(&a, &b), Ordering::Greater);
+        assert_eq!(computed.get(), 2);
+
+        // computing the key of "C" evicts the least recently used key, of "a"
+        assert_eq!(collator.cmp(&a, &c), Ordering::Less);
+        assert_eq!(computed.get(), 3);
+        assert_eq!(collator.len(), 2);
+
+        assert_eq!(collator.cmp(&b, &c), Ordering::Less);
+        assert_eq!(computed.get(), 4);
+
+        collator.clear();
+        assert!(collator.is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+
+use caseless::Caseless;
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// The case folding rules used by a [`CaseFoldCollator`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CaseFolding {
+    /// Full Unicode case folding, which folds e.g. "ß" to "ss"
+    #[default]
+    Full,
+
+    /// Full Unicode case folding with the Turkic mappings of dotted and dotless "i",
+    /// so that "I" folds to "ı" and "İ" folds to "i"
+    Turkic,
+}
+
+/// A case-insensitive collator for strings which accounts for locale-specific case folding,
+/// without the weight of a full ICU dependency.
+///
+/// Strings are decomposed into canonical form (NFD) and case-folded according to the given
+/// [`CaseFolding`] before comparison by code point. Like the `SearchCollator`, this is intended
+/// for matching rather than a locale-correct display order.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CaseFoldCollator, CollateRef};
+///
+/// let german = CaseFoldCollator::german();
+/// assert_eq!(german.cmp_ref("Straße", "STRASSE"), Ordering::Equal);
+///
+/// let turkish = CaseFoldCollator::turkish();
+/// assert_eq!(turkish.cmp_ref("DİYARBAKIR", "diyarbakır"), Ordering::Equal);
+/// assert_ne!(turkish.cmp_ref("I", "i"), Ordering::Equal);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CaseFoldCollator {
+    folding: CaseFolding,
+}
+
+impl CaseFoldCollator {
+    /// Construct a new [`CaseFoldCollator`] with the given [`CaseFolding`] rules.
+    pub fn new(folding: CaseFolding) -> Self {
+        Self { folding }
+    }
+
+    /// Construct a [`CaseFoldCollator`] for German text, which folds "ß" and "ẞ" to "ss".
+    pub fn german() -> Self {
+        Self::new(CaseFolding::Full)
+    }
+
+    /// Construct a [`CaseFoldCollator`] for Turkish text, which distinguishes dotted and
+    /// dotless "i".
+    pub fn turkish() -> Self {
+        Self::new(CaseFolding::Turkic)
+    }
+
+    /// Construct a [`CaseFoldCollator`] for Azerbaijani text, which distinguishes dotted and
+    /// dotless "i".
+    pub fn azerbaijani() -> Self {
+        Self::new(CaseFolding::Turkic)
+    }
+
+    /// The [`CaseFolding`] rules of this collator.
+    pub fn folding(&self) -> CaseFolding {
+        self.folding
+    }
+
+    /// Return the case-folded key of the given `value`, which collates equal to `value`.
+    pub fn fold_key(&self, value: &str) -> String {
+        self.fold(value).collect()
+    }
+
+    fn fold<'a>(&self, value: &'a str) -> impl Iterator<Item = char> + 'a {
+        let turkic = self.folding == CaseFolding::Turkic;
+        let mut chars = value.nfd().peekable();
+
+        // in Turkic languages "I" lower-cases to "ı" and "İ" (decomposed as "I\u{307}") to "i"
+        std::iter::from_fn(move || match chars.next()? {
+            'I' if turkic => {
+                if chars.next_if_eq(&'\u{307}').is_some() {
+                    Some('i')
+                } else {
+                    Some('ı')
+                }
+            }
+            c => Some(c),
+        })
+        .default_case_fold()
+        .nfd()
+    }
+}
+
+impl Collate for CaseFoldCollator {
+    type Value = String;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_str(), right.as_str())
+    }
+}
+
+impl CollateRef<str> for CaseFoldCollator {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        self.fold(left).cmp(self.fold(right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_fold_collator() {
+        let full = CaseFoldCollator::default();
+        assert_eq!(full.cmp_ref("MASSE", "Maße"), Ordering::Equal);
+        assert_eq!(full.cmp_ref("Maẞe", "masse"), Ordering::Equal);
+        assert_eq!(full.cmp_ref("I", "i"), Ordering::Equal);
+        assert_eq!(full.cmp_ref("Café", "CAFE\u{301}"), Ordering::Equal);
+        assert_eq!(full.fold_key("Iİı"), "ii\u{307}ı");
+
+        let turkish = CaseFoldCollator::turkish();
+        assert_eq!(turkish.cmp_ref("İstanbul", "istanbul"), Ordering::Equal);
+        assert_eq!(
+            turkish.cmp_ref("I\u{307}stanbul", "istanbul"),
+            Ordering::Equal
+        );
+        assert_eq!(turkish.cmp_ref("ISPARTA", "ısparta"), Ordering::Equal);
+        assert_eq!(turkish.cmp_ref("ISPARTA", "isparta"), Ordering::Greater);
+        assert_eq!(turkish.fold_key("Iİı"), "ıiı");
+    }
+}
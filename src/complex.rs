@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use num_complex::Complex;
+use num_traits::Float;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// The order in which a [`ComplexCollator`] collates complex numbers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ComplexOrder {
+    /// Collate by norm (i.e. absolute value), then lexicographically to break ties
+    #[default]
+    Norm,
+
+    /// Collate by real part, then by imaginary part
+    Lexicographic,
+
+    /// Collate by argument (i.e. phase angle) in the range `(-π, π]`, then by norm
+    Argument,
+}
+
+/// A collator for complex numbers, which collates them in a selectable [`ComplexOrder`].
+///
+/// This supports any real type which implements [`num_traits::Float`].
+/// Real values are compared using the IEEE 754 total order, as in a
+/// [`FloatCollator`](crate::FloatCollator), so every mode is a total order
+/// and only identical complex numbers collate equal (except that NaNs of the same sign
+/// collate equal regardless of their payload).
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, ComplexCollator, ComplexOrder};
+/// use num_complex::Complex;
+///
+/// let a = Complex::new(3.0, 4.0);
+/// let b = Complex::new(-6.0, 0.0);
+///
+/// let collator = ComplexCollator::<f64>::new(ComplexOrder::Norm);
+/// assert_eq!(collator.cmp(&a, &b), Ordering::Less);
+///
+/// let collator = ComplexCollator::<f64>::new(ComplexOrder::Lexicographic);
+/// assert_eq!(collator.cmp(&a, &b), Ordering::Greater);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct ComplexCollator<T> {
+    order: ComplexOrder,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<T>,
+}
+
+impl<T> ComplexCollator<T> {
+    /// Construct a new [`ComplexCollator`] with the given [`ComplexOrder`].
+    pub fn new(order: ComplexOrder) -> Self {
+        Self {
+            order,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The [`ComplexOrder`] of this collator.
+    pub fn order(&self) -> ComplexOrder {
+        self.order
+    }
+}
+
+impl<T> Default for ComplexCollator<T> {
+    fn default() -> Self {
+        Self::new(ComplexOrder::default())
+    }
+}
+
+impl<T> Clone for ComplexCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ComplexCollator<T> {}
+
+impl<T> std::fmt::Debug for ComplexCollator<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ComplexCollator")
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ComplexCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+
+impl<T> Eq for ComplexCollator<T> {}
+
+impl<T: Float> Collate for ComplexCollator<T> {
+    type Value = Complex<T>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        let lexicographic =
+            || total_cmp(left.re, right.re).then_with(|| total_cmp(left.im, right.im));
+
+        match self.order {
+            ComplexOrder::Norm => total_cmp(left.norm(), right.norm()).then_with(lexicographic),
+            ComplexOrder::Lexicographic => lexicographic(),
+            ComplexOrder::Argument => total_cmp(left.arg(), right.arg())
+                .then_with(|| total_cmp(left.norm(), right.norm()))
+                .then_with(lexicographic),
+        }
+    }
+}
+
+// the IEEE 754 total order, except that NaNs with the same sign are equal
+fn total_cmp<T: Float>(left: T, right: T) -> Ordering {
+    // negative NaNs collate first and positive NaNs collate last
+    let class = |value: T| match (value.is_nan(), value.is_sign_negative()) {
+        (true, true) => 0,
+        (false, _) => 1,
+        (true, false) => 2,
+    };
+
+    class(left).cmp(&class(right)).then_with(|| {
+        match left.partial_cmp(&right) {
+            Some(Ordering::Equal) => {
+                // distinguish -0.0 from 0.0
+                right.is_sign_negative().cmp(&left.is_sign_negative())
+            }
+            Some(order) => order,
+            None => Ordering::Equal,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_cmp() {
+        let values = [
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            -1.0,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+
+        for l in values {
+            for r in values {
+                assert_eq!(total_cmp(l, r), l.total_cmp(&r), "{l} vs {r}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_complex_collator() {
+        let values = [
+            Complex::new(1.0f32, 0.0),
+            Complex::new(0.0, 1.0),
+            Complex::new(-1.0, 0.0),
+            Complex::new(0.0, -2.0),
+        ];
+
+        let sorted = |order| {
+            let collator = ComplexCollator::<f32>::new(order);
+            let mut values = values.to_vec();
+            values.sort_by(|l, r| collator.cmp(l, r));
+            values
+        };
+
+        assert_eq!(
+            sorted(ComplexOrder::Norm),
+            [values[2], values[1], values[0], values[3]]
+        );
+
+        assert_eq!(
+            sorted(ComplexOrder::Lexicographic),
+            [values[2], values[3], values[1], values[0]]
+        );
+
+        assert_eq!(
+            sorted(ComplexOrder::Argument),
+            [values[3], values[0], values[1], values[2]]
+        );
+    }
+}
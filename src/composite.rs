@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Collate, Direction, NullOrder};
+
+type FieldCmp<R> = Arc<dyn Fn(&R, &R) -> Ordering + Send + Sync>;
+
+struct CompositeKey<R> {
+    direction: Direction,
+    nulls: NullOrder,
+    cmp: FieldCmp<R>,
+}
+
+impl<R> Clone for CompositeKey<R> {
+    fn clone(&self) -> Self {
+        Self {
+            direction: self.direction,
+            nulls: self.nulls,
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+/// A collator for records of type `R`, assembled at runtime from a list of keys, each of which
+/// collates one (nullable) field of the record in a given [`Direction`] and [`NullOrder`].
+///
+/// This is useful to construct an `ORDER BY` comparator from user input,
+/// where the number and types of the keys are not known at compile time.
+/// Records are collated by each key in turn, and collate equal if all their keys are equal.
+///
+/// Since the fields and collators of its keys can't be compared, a [`CompositeCollator`]
+/// with any keys is only equal to its own clones.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, Collator, CompositeCollator, Direction, NullOrder};
+///
+/// struct Person {
+///     name: String,
+///     age: Option<u32>,
+/// }
+///
+/// let collator = CompositeCollator::new()
+///     .key(
+///         Collator::<u32>::default(),
+///         |person: &Person| person.age.as_ref(),
+///         Direction::Descending,
+///         NullOrder::Last,
+///     )
+///     .key(
+///         Collator::<String>::default(),
+///         |person: &Person| Some(&person.name),
+///         Direction::Ascending,
+///         NullOrder::Last,
+///     );
+///
+/// let mut people = vec![
+///     Person { name: "Carol".into(), age: None },
+///     Person { name: "Bob".into(), age: Some(30) },
+///     Person { name: "Alice".into(), age: Some(30) },
+///     Person { name: "Dave".into(), age: Some(40) },
+/// ];
+///
+/// people.sort_by(|l, r| collator.cmp(l, r));
+///
+/// let names = people.iter().map(|person| person.name.as_str()).collect::<Vec<_>>();
+/// assert_eq!(names, ["Dave", "Alice", "Bob", "Carol"]);
+/// ```
+pub struct CompositeCollator<R> {
+    keys: Vec<CompositeKey<R>>,
+}
+
+impl<R> CompositeCollator<R> {
+    /// Construct a new [`CompositeCollator`] with no keys, which finds all records equal.
+    pub fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    /// Add a key which collates the `field` of each record using the given `collator`,
+    /// to break ties between records whose previous keys are equal.
+    pub fn key<C, F>(
+        mut self,
+        collator: C,
+        field: F,
+        direction: Direction,
+        nulls: NullOrder,
+    ) -> Self
+    where
+        C: Collate + Send + Sync + 'static,
+        F: Fn(&R) -> Option<&C::Value> + Send + Sync + 'static,
+    {
+        let cmp = move |left: &R, right: &R| {
+            nulls.cmp(field(left), field(right), |l, r| {
+                direction.apply(collator.cmp(l, r))
+            })
+        };
+
+        self.keys.push(CompositeKey {
+            direction,
+            nulls,
+            cmp: Arc::new(cmp),
+        });
+
+        self
+    }
+
+    /// The number of keys of this collator.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Return `true` if this collator has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<R> Clone for CompositeCollator<R> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<R> Default for CompositeCollator<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> fmt::Debug for CompositeCollator<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let keys = self.keys.iter().map(|key| (key.direction, key.nulls));
+        f.debug_tuple("CompositeCollator")
+            .field(&keys.collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<R> PartialEq for CompositeCollator<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys.len() == other.keys.len()
+            && self
+                .keys
+                .iter()
+                .zip(&other.keys)
+                .all(|(l, r)| Arc::ptr_eq(&l.cmp, &r.cmp))
+    }
+}
+
+impl<R> Eq for CompositeCollator<R> {}
+
+impl<R> Collate for CompositeCollator<R> {
+    type Value = R;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        for key in &self.keys {
+            let order = (key.cmp)(left, right);
+
+            if order != Ordering::Equal {
+                return order;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_composite_collator() {
+        let collator = CompositeCollator::<(Option<u32>, String)>::default();
+        assert!(collator.is_empty());
+        assert_eq!(
+            collator.cmp(&(Some(1), "b".into()), &(None, "a".into())),
+            Ordering::Equal
+        );
+
+        let collator = collator
+            .key(
+                Collator::<u32>::default(),
+                |(number, _)| number.as_ref(),
+                Direction::Ascending,
+                NullOrder::First,
+            )
+            .key(
+                Collator::<String>::default(),
+                |(_, name)| Some(name),
+                Direction::Descending,
+                NullOrder::Last,
+            );
+
+        let mut records = vec![
+            (Some(2), "a".to_string()),
+            (Some(1), "a".to_string()),
+            (None, "z".to_string()),
+            (Some(1), "b".to_string()),
+        ];
+
+        records.sort_by(|l, r| collator.cmp(l, r));
+
+        assert_eq!(
+            records,
+            vec![
+                (None, "z".to_string()),
+                (Some(1), "b".to_string()),
+                (Some(1), "a".to_string()),
+                (Some(2), "a".to_string()),
+            ]
+        );
+
+        assert_eq!(collator, collator.clone());
+
+        let by_name = |key: fn(&(Option<u32>, String)) -> Option<&String>| {
+            let collator = Collator::<String>::default();
+            CompositeCollator::new().key(collator, key, Direction::Ascending, NullOrder::Last)
+        };
+
+        assert_ne!(by_name(|(_, name)| Some(name)), by_name(|_| None));
+    }
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+use chrono::{DateTime, TimeZone};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// A collator for [`DateTime`]s which collates them by instant, regardless of time zone.
+///
+/// This is useful to collate a `DateTime<FixedOffset>` from sources with different UTC offsets.
+/// Two date-times which represent the same instant collate equal even if their offsets differ,
+/// so when merging streams, only the first of them is kept.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use chrono::DateTime;
+/// use collate::{Collate, DateTimeCollator};
+///
+/// let collator = DateTimeCollator::default();
+///
+/// let paris = DateTime::parse_from_rfc3339("2024-06-01T12:00:00+02:00").unwrap();
+/// let london = DateTime::parse_from_rfc3339("2024-06-01T11:00:00+01:00").unwrap();
+/// let new_york = DateTime::parse_from_rfc3339("2024-06-01T07:00:00-04:00").unwrap();
+///
+/// assert_eq!(collator.cmp(&paris, &london), Ordering::Equal);
+/// assert_eq!(collator.cmp(&new_york, &paris), Ordering::Greater);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct DateTimeCollator<Tz> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<Tz>,
+}
+
+impl<Tz> Default for DateTimeCollator<Tz> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Tz> Clone for DateTimeCollator<Tz> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Tz> Copy for DateTimeCollator<Tz> {}
+
+impl<Tz> PartialEq for DateTimeCollator<Tz> {
+    fn eq(&self, _other: &Self) -> bool {
+        // this collator has no configuration state
+        true
+    }
+}
+
+impl<Tz> Eq for DateTimeCollator<Tz> {}
+
+impl<Tz: TimeZone> Collate for DateTimeCollator<Tz> {
+    type Value = DateTime<Tz>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        left.naive_utc().cmp(&right.naive_utc())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    #[test]
+    fn test_datetime_collator() {
+        let collator = DateTimeCollator::<FixedOffset>::default();
+
+        let east = FixedOffset::east_opt(9 * 3600).expect("offset");
+        let west = FixedOffset::west_opt(5 * 3600).expect("offset");
+
+        let instant = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let tokyo = instant.with_timezone(&east);
+        let lima = instant.with_timezone(&west);
+
+        // the local time in Lima is earlier, but the instant is the same
+        assert!(lima.naive_local() < tokyo.naive_local());
+        assert_eq!(collator.cmp(&tokyo, &lima), Ordering::Equal);
+
+        let later = (instant + chrono::Duration::seconds(1)).with_timezone(&west);
+        assert_eq!(collator.cmp(&tokyo, &later), Ordering::Less);
+    }
+}
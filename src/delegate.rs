@@ -0,0 +1,95 @@
+/// Implement [`Collate`](crate::Collate) for a newtype collator by delegating to the collator
+/// which it wraps, optionally projecting a field of each value or reversing the collation,
+/// e.g. to collate the many ID newtypes of a downstream crate without repeating the same `impl`.
+///
+/// The newtype must be a tuple struct whose only field is the inner collator, and must implement
+/// `Eq` (which [`Collate`](crate::Collate) requires).
+///
+/// The forms accepted are:
+///  - `delegate_collate!(Name(Inner))` to collate the same values as `Inner`
+///  - `delegate_collate!(Name(Inner), reverse)` to collate them in reverse order
+///  - `delegate_collate!(Name(Inner) => Value, |value| field)` to collate values of type `Value`
+///    by the field borrowed from each one
+///  - `delegate_collate!(Name(Inner) => Value, |value| field, reverse)` to collate values of type
+///    `Value` by the field borrowed from each one, in reverse order
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{delegate_collate, Collate, Collator};
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// struct UserId(u64);
+///
+/// #[derive(Default, Eq, PartialEq)]
+/// struct UserIdCollator(Collator<u64>);
+///
+/// delegate_collate!(UserIdCollator(Collator<u64>) => UserId, |id| &id.0);
+///
+/// #[derive(Default, Eq, PartialEq)]
+/// struct NewestFirst(Collator<u64>);
+///
+/// delegate_collate!(NewestFirst(Collator<u64>) => UserId, |id| &id.0, reverse);
+///
+/// assert_eq!(UserIdCollator::default().cmp(&UserId(1), &UserId(2)), Ordering::Less);
+/// assert_eq!(NewestFirst::default().cmp(&UserId(1), &UserId(2)), Ordering::Greater);
+/// ```
+#[macro_export]
+macro_rules! delegate_collate {
+    ($name:ident($inner:ty)) => {
+        $crate::delegate_collate!(
+            @impl $name($inner), <$inner as $crate::Collate>::Value, value, value, false
+        );
+    };
+    ($name:ident($inner:ty), reverse) => {
+        $crate::delegate_collate!(
+            @impl $name($inner), <$inner as $crate::Collate>::Value, value, value, true
+        );
+    };
+    ($name:ident($inner:ty) => $value:ty, |$arg:ident| $project:expr) => {
+        $crate::delegate_collate!(@impl $name($inner), $value, $arg, $project, false);
+    };
+    ($name:ident($inner:ty) => $value:ty, |$arg:ident| $project:expr, reverse) => {
+        $crate::delegate_collate!(@impl $name($inner), $value, $arg, $project, true);
+    };
+    (@impl $name:ident($inner:ty), $value:ty, $arg:ident, $project:expr, $reverse:expr) => {
+        impl $crate::Collate for $name {
+            type Value = $value;
+
+            fn cmp(&self, left: &Self::Value, right: &Self::Value) -> ::std::cmp::Ordering {
+                fn project($arg: &$value) -> &<$inner as $crate::Collate>::Value {
+                    $project
+                }
+
+                let order = $crate::Collate::cmp(&self.0, project(left), project(right));
+
+                if $reverse {
+                    order.reverse()
+                } else {
+                    order
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use crate::{Collate, Collator};
+
+    #[derive(Default, Eq, PartialEq)]
+    struct Descending(Collator<String>);
+
+    delegate_collate!(Descending(Collator<String>), reverse);
+
+    #[test]
+    fn test_delegate_collate() {
+        let collator = Descending::default();
+        let (a, b) = ("a".to_string(), "b".to_string());
+
+        assert_eq!(collator.cmp(&a, &b), Ordering::Greater);
+        assert_eq!(collator.cmp(&b, &b), Ordering::Equal);
+    }
+}
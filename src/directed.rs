@@ -0,0 +1,64 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// The direction in which to collate values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Direction {
+    /// Collate values in ascending order
+    #[default]
+    Ascending,
+
+    /// Collate values in descending order
+    Descending,
+}
+
+impl Direction {
+    /// Apply this [`Direction`] to the given `order`.
+    pub fn apply(self, order: Ordering) -> Ordering {
+        match self {
+            Self::Ascending => order,
+            Self::Descending => order.reverse(),
+        }
+    }
+}
+
+/// A collator which applies a [`Direction`] to the collation of another collator.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DirectedCollator<C> {
+    collator: C,
+    direction: Direction,
+}
+
+impl<C> DirectedCollator<C> {
+    /// Construct a new [`DirectedCollator`].
+    pub fn new(collator: C, direction: Direction) -> Self {
+        Self {
+            collator,
+            direction,
+        }
+    }
+
+    /// Borrow the underlying collator.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+
+    /// The [`Direction`] of this collator.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+}
+
+impl<C: Collate> Collate for DirectedCollator<C> {
+    type Value = C::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.direction.apply(self.collator.cmp(left, right))
+    }
+}
@@ -0,0 +1,299 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef, Collator};
+
+/// A dynamically-typed value, e.g. from a database column without a fixed type.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Dynamic {
+    /// A signed integer
+    Int(i64),
+
+    /// A floating-point number
+    Float(f64),
+
+    /// A string
+    String(String),
+
+    /// A byte string
+    Bytes(Vec<u8>),
+
+    /// A tuple of dynamically-typed values
+    Tuple(Vec<Dynamic>),
+}
+
+impl Dynamic {
+    /// The [`DynType`] of this value.
+    pub fn dyn_type(&self) -> DynType {
+        match self {
+            Self::Int(_) | Self::Float(_) => DynType::Number,
+            Self::String(_) => DynType::String,
+            Self::Bytes(_) => DynType::Bytes,
+            Self::Tuple(_) => DynType::Tuple,
+        }
+    }
+}
+
+/// The type of a [`Dynamic`] value, for the purpose of collation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DynType {
+    /// An integer or floating-point number
+    Number,
+
+    /// A string
+    String,
+
+    /// A byte string
+    Bytes,
+
+    /// A tuple
+    Tuple,
+}
+
+const DYN_TYPES: usize = 4;
+
+/// The error returned by [`DynCollator::try_new`] when a type precedence table
+/// contains the given [`DynType`] more than once.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PrecedenceError(pub DynType);
+
+impl fmt::Display for PrecedenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "duplicate type in precedence table: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for PrecedenceError {}
+
+/// A collator for [`Dynamic`] values, which collates values of different types according to
+/// a type precedence table, and values of the same type as follows:
+///  - numbers numerically, so that e.g. `Int(1)` and `Float(1.0)` collate equal, using the
+///    IEEE 754 total order for floats (so NaN collates after all integers) except that
+///    `-0.0` and `0.0` collate equal, like `Int(0)`,
+///  - strings using a string collator (by default, a [`Collator<String>`]),
+///  - byte strings lexicographically, and
+///  - tuples lexicographically using this collator.
+///
+/// To collate composite keys of dynamically-typed columns, use a
+/// [`LexCollator<DynCollator>`](crate::LexCollator).
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, Collator, DynCollator, DynType, Dynamic};
+///
+/// let collator = DynCollator::default();
+/// assert_eq!(collator.cmp(&Dynamic::Int(2), &Dynamic::Float(1.5)), Ordering::Greater);
+/// assert_eq!(collator.cmp(&Dynamic::Int(2), &Dynamic::String("1".into())), Ordering::Less);
+///
+/// let precedence = [DynType::String, DynType::Number, DynType::Bytes, DynType::Tuple];
+/// let collator = DynCollator::new(precedence, Collator::default());
+/// assert_eq!(collator.cmp(&Dynamic::Int(2), &Dynamic::String("1".into())), Ordering::Greater);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "DynCollatorState<S>")
+)]
+pub struct DynCollator<S = Collator<String>> {
+    precedence: [DynType; DYN_TYPES],
+    strings: S,
+}
+
+// the serialized form of a `DynCollator`, which is validated when it's deserialized
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct DynCollatorState<S> {
+    precedence: [DynType; DYN_TYPES],
+    strings: S,
+}
+
+#[cfg(feature = "serde")]
+impl<S> TryFrom<DynCollatorState<S>> for DynCollator<S> {
+    type Error = PrecedenceError;
+
+    fn try_from(state: DynCollatorState<S>) -> Result<Self, Self::Error> {
+        Self::try_new(state.precedence, state.strings)
+    }
+}
+
+impl<S> DynCollator<S> {
+    /// Construct a new [`DynCollator`] with the given type `precedence`, from first to last,
+    /// which uses the given collator for strings.
+    ///
+    /// Panics if `precedence` does not contain each [`DynType`] exactly once.
+    pub fn new(precedence: [DynType; DYN_TYPES], strings: S) -> Self {
+        match Self::try_new(precedence, strings) {
+            Ok(collator) => collator,
+            Err(cause) => panic!("{cause}"),
+        }
+    }
+
+    /// Construct a new [`DynCollator`] like [`DynCollator::new`], or return a [`PrecedenceError`]
+    /// if `precedence` does not contain each [`DynType`] exactly once.
+    pub fn try_new(precedence: [DynType; DYN_TYPES], strings: S) -> Result<Self, PrecedenceError> {
+        for (i, dyn_type) in precedence.iter().enumerate() {
+            if precedence[..i].contains(dyn_type) {
+                return Err(PrecedenceError(*dyn_type));
+            }
+        }
+
+        Ok(Self {
+            precedence,
+            strings,
+        })
+    }
+
+    /// The type precedence table of this collator.
+    pub fn precedence(&self) -> [DynType; DYN_TYPES] {
+        self.precedence
+    }
+
+    fn rank(&self, dyn_type: DynType) -> usize {
+        // the precedence table contains every type, since it has no duplicates
+        self.precedence
+            .iter()
+            .position(|t| *t == dyn_type)
+            .unwrap_or(DYN_TYPES)
+    }
+}
+
+impl Default for DynCollator {
+    fn default() -> Self {
+        let precedence = [
+            DynType::Number,
+            DynType::String,
+            DynType::Bytes,
+            DynType::Tuple,
+        ];
+        Self::new(precedence, Collator::default())
+    }
+}
+
+impl<S: Collate<Value = String>> Collate for DynCollator<S> {
+    type Value = Dynamic;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        match (left, right) {
+            (Dynamic::Int(l), Dynamic::Int(r)) => l.cmp(r),
+            (Dynamic::Float(l), Dynamic::Float(r)) => unsign_zero(*l).total_cmp(&unsign_zero(*r)),
+            (Dynamic::Int(l), Dynamic::Float(r)) => cmp_int_float(*l, *r),
+            (Dynamic::Float(l), Dynamic::Int(r)) => cmp_int_float(*r, *l).reverse(),
+            (Dynamic::String(l), Dynamic::String(r)) => self.strings.cmp(l, r),
+            (Dynamic::Bytes(l), Dynamic::Bytes(r)) => l.cmp(r),
+            (Dynamic::Tuple(l), Dynamic::Tuple(r)) => self.cmp_ref(l.as_slice(), r.as_slice()),
+            (l, r) => self.rank(l.dyn_type()).cmp(&self.rank(r.dyn_type())),
+        }
+    }
+}
+
+impl<S: Collate<Value = String>> CollateRef<[Dynamic]> for DynCollator<S> {
+    fn cmp_ref(&self, left: &[Dynamic], right: &[Dynamic]) -> Ordering {
+        for (l, r) in left.iter().zip(right) {
+            match self.cmp(l, r) {
+                Ordering::Equal => {}
+                order => return order,
+            }
+        }
+
+        left.len().cmp(&right.len())
+    }
+}
+
+// replace a negative zero with a positive zero, so that both collate equal to `Int(0)`
+fn unsign_zero(float: f64) -> f64 {
+    if float == 0.0 {
+        0.0
+    } else {
+        float
+    }
+}
+
+// compare an integer to a float exactly, consistent with the IEEE 754 total order
+fn cmp_int_float(int: i64, float: f64) -> Ordering {
+    const MAX: f64 = 9_223_372_036_854_775_808.; // 2^63
+
+    if float.is_nan() {
+        if float.is_sign_negative() {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    } else if float >= MAX {
+        Ordering::Less
+    } else if float < -MAX {
+        Ordering::Greater
+    } else {
+        let trunc = float.trunc();
+
+        // the truncated float is in the range of i64, so this cast is exact
+        int.cmp(&(trunc as i64))
+            .then_with(|| 0f64.partial_cmp(&(float - trunc)).expect("fraction"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::check_collate;
+    use crate::LexCollator;
+
+    #[test]
+    fn test_dyn_collator() {
+        let samples = [
+            Dynamic::Float(f64::NEG_INFINITY),
+            Dynamic::Int(i64::MIN),
+            Dynamic::Float(-1.5),
+            Dynamic::Int(-1),
+            Dynamic::Float(-0.5),
+            Dynamic::Float(-0.0),
+            Dynamic::Int(0),
+            Dynamic::Float(0.0),
+            Dynamic::Float(0.5),
+            Dynamic::Int(1),
+            Dynamic::Float(1.0),
+            Dynamic::Int(i64::MAX),
+            Dynamic::Float(9.3e18),
+            Dynamic::Float(f64::NAN),
+            Dynamic::String("a".into()),
+            Dynamic::Bytes(vec![0]),
+            Dynamic::Tuple(vec![]),
+            Dynamic::Tuple(vec![Dynamic::Int(1), Dynamic::String("b".into())]),
+        ];
+
+        let collator = DynCollator::default();
+        assert_eq!(check_collate(&collator, &samples), Ok(()));
+
+        for pair in samples.windows(2) {
+            assert_ne!(collator.cmp(&pair[0], &pair[1]), Ordering::Greater);
+        }
+
+        assert_eq!(
+            collator.cmp(&Dynamic::Int(1), &Dynamic::Float(1.0)),
+            Ordering::Equal
+        );
+
+        let keys = LexCollator::new(collator);
+        let left = vec![Dynamic::Int(1), Dynamic::Bytes(vec![1])];
+        let right = vec![Dynamic::Float(1.0), Dynamic::Tuple(vec![])];
+        assert_eq!(keys.cmp(&left, &right), Ordering::Less);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_dyn_collator_serde() {
+        let collator = DynCollator::default();
+        let serialized = serde_json::to_string(&collator).expect("JSON");
+        let deserialized: DynCollator = serde_json::from_str(&serialized).expect("collator");
+        assert!(collator == deserialized);
+
+        let duplicate = r#"{"precedence":["Number","Number","Bytes","Tuple"],"strings":{}}"#;
+        assert!(serde_json::from_str::<DynCollator>(duplicate).is_err());
+    }
+}
@@ -0,0 +1,613 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateKey, TryCollate};
+
+/// Where a [`FloatCollator`] collates NaN values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NanPolicy {
+    /// Collate NaN values according to the IEEE 754 `totalOrder` predicate, i.e. negative NaNs
+    /// before all other values and positive NaNs after all other values
+    #[default]
+    TotalOrder,
+
+    /// Collate all NaN values before all other values
+    First,
+
+    /// Collate all NaN values after all other values
+    Last,
+}
+
+/// The error returned by [`StrictFloatCollator::try_cmp`](TryCollate::try_cmp) when comparing
+/// a NaN value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NanError;
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("cannot collate NaN")
+    }
+}
+
+impl std::error::Error for NanError {}
+
+/// A collator for the floating-point types `f32` and `f64`, using the IEEE 754 `totalOrder`
+/// predicate (see [`f64::total_cmp`]) and a configurable [`NanPolicy`].
+///
+/// By default, values are collated in this order: negative NaNs, negative infinity,
+/// negative numbers, negative zero, positive zero, positive numbers, positive infinity,
+/// positive NaNs. Note that this means `-0.0` and `0.0` are **not** equal, and that NaN values
+/// with different bit patterns are not equal to each other. With [`NanPolicy::First`] or
+/// [`NanPolicy::Last`], all NaN values are equal to each other.
+///
+/// Optionally, `-0.0` and `0.0` can be collated equal (as in numeric comparison), and subnormal
+/// values can be flushed to zero, e.g. to match the collation of a lossy key encoding.
+///
+/// To reject NaN values rather than collate them, use a [`StrictFloatCollator`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, FloatCollator, NanPolicy};
+///
+/// let collator = FloatCollator::<f64>::default();
+/// assert_eq!(collator.cmp(&1.5, &2.0), Ordering::Less);
+/// assert_eq!(collator.cmp(&f64::NAN, &f64::INFINITY), Ordering::Greater);
+/// assert_eq!(collator.cmp(&-0.0, &0.0), Ordering::Less);
+///
+/// let collator = FloatCollator::<f64>::new(NanPolicy::First);
+/// assert_eq!(collator.cmp(&f64::NAN, &f64::NEG_INFINITY), Ordering::Less);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct FloatCollator<T> {
+    nans: NanPolicy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    equal_zeros: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    flush_subnormals: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<T>,
+}
+
+impl<T> FloatCollator<T> {
+    /// Construct a new [`FloatCollator`] with the given [`NanPolicy`].
+    pub fn new(nans: NanPolicy) -> Self {
+        Self {
+            nans,
+            equal_zeros: false,
+            flush_subnormals: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Set whether to collate `-0.0` and `0.0` as equal.
+    pub fn equal_zeros(mut self, equal_zeros: bool) -> Self {
+        self.equal_zeros = equal_zeros;
+        self
+    }
+
+    /// Set whether to collate subnormal values as zero (with the same sign).
+    pub fn flush_subnormals(mut self, flush_subnormals: bool) -> Self {
+        self.flush_subnormals = flush_subnormals;
+        self
+    }
+
+    /// The [`NanPolicy`] of this collator.
+    pub fn nan_policy(&self) -> NanPolicy {
+        self.nans
+    }
+}
+
+impl<T> Default for FloatCollator<T> {
+    fn default() -> Self {
+        Self::new(NanPolicy::default())
+    }
+}
+
+impl<T> Clone for FloatCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FloatCollator<T> {}
+
+impl<T> fmt::Debug for FloatCollator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FloatCollator")
+            .field("nans", &self.nans)
+            .field("equal_zeros", &self.equal_zeros)
+            .field("flush_subnormals", &self.flush_subnormals)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for FloatCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nans == other.nans
+            && self.equal_zeros == other.equal_zeros
+            && self.flush_subnormals == other.flush_subnormals
+    }
+}
+
+impl<T> Eq for FloatCollator<T> {}
+
+macro_rules! float_collator {
+    ($t:ty, $bits:ty) => {
+        impl FloatCollator<$t> {
+            fn normalize(&self, value: $t) -> $t {
+                let value = if self.flush_subnormals && value.is_subnormal() {
+                    (0.0 as $t).copysign(value)
+                } else {
+                    value
+                };
+
+                if self.equal_zeros && value == 0.0 {
+                    0.0
+                } else {
+                    value
+                }
+            }
+        }
+
+        impl Collate for FloatCollator<$t> {
+            type Value = $t;
+
+            fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+                let (left, right) = (&self.normalize(*left), &self.normalize(*right));
+                let (l_nan, r_nan) = (left.is_nan(), right.is_nan());
+
+                match self.nans {
+                    NanPolicy::TotalOrder => left.total_cmp(right),
+                    _ if !l_nan && !r_nan => left.total_cmp(right),
+                    NanPolicy::First => r_nan.cmp(&l_nan),
+                    NanPolicy::Last => l_nan.cmp(&r_nan),
+                }
+            }
+        }
+
+        impl TryCollate for StrictFloatCollator<$t> {
+            type Value = $t;
+            type Error = NanError;
+
+            fn try_cmp(
+                &self,
+                left: &Self::Value,
+                right: &Self::Value,
+            ) -> Result<Ordering, Self::Error> {
+                if left.is_nan() || right.is_nan() {
+                    Err(NanError)
+                } else {
+                    Ok(self.collator.cmp(left, right))
+                }
+            }
+        }
+
+        impl CollateKey for FloatCollator<$t> {
+            fn write_key(&self, value: &$t, key: &mut Vec<u8>) {
+                let value = self.normalize(*value);
+
+                let bits = match self.nans {
+                    NanPolicy::First if value.is_nan() => <$bits>::MIN,
+                    NanPolicy::Last if value.is_nan() => <$bits>::MAX,
+                    _ => {
+                        // the IEEE 754 total order of the bits, as an unsigned integer
+                        let bits = value.to_bits();
+                        if bits >> (<$bits>::BITS - 1) == 1 {
+                            !bits
+                        } else {
+                            bits | (1 << (<$bits>::BITS - 1))
+                        }
+                    }
+                };
+
+                key.extend_from_slice(&bits.to_be_bytes())
+            }
+        }
+    };
+}
+
+/// A fallible collator for the floating-point types `f32` and `f64`, which collates values
+/// like a [`FloatCollator`] but returns a [`NanError`] when comparing a NaN value.
+///
+/// This only implements [`TryCollate`], since NaN is a valid value of a floating-point type.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{NanError, StrictFloatCollator, TryCollate};
+///
+/// let collator = StrictFloatCollator::<f64>::new().equal_zeros(true);
+/// assert_eq!(collator.try_cmp(&1.0, &2.0), Ok(Ordering::Less));
+/// assert_eq!(collator.try_cmp(&-0.0, &0.0), Ok(Ordering::Equal));
+/// assert_eq!(collator.try_cmp(&1.0, &f64::NAN), Err(NanError));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct StrictFloatCollator<T> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    collator: FloatCollator<T>,
+}
+
+impl<T> StrictFloatCollator<T> {
+    /// Construct a new [`StrictFloatCollator`].
+    pub fn new() -> Self {
+        Self {
+            collator: FloatCollator::default(),
+        }
+    }
+
+    /// Set whether to collate `-0.0` and `0.0` as equal.
+    pub fn equal_zeros(self, equal_zeros: bool) -> Self {
+        Self {
+            collator: self.collator.equal_zeros(equal_zeros),
+        }
+    }
+
+    /// Set whether to collate subnormal values as zero (with the same sign).
+    pub fn flush_subnormals(self, flush_subnormals: bool) -> Self {
+        Self {
+            collator: self.collator.flush_subnormals(flush_subnormals),
+        }
+    }
+}
+
+impl<T> Default for StrictFloatCollator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for StrictFloatCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for StrictFloatCollator<T> {}
+
+impl<T> fmt::Debug for StrictFloatCollator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StrictFloatCollator")
+            .field("equal_zeros", &self.collator.equal_zeros)
+            .field("flush_subnormals", &self.collator.flush_subnormals)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for StrictFloatCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.collator == other.collator
+    }
+}
+
+impl<T> Eq for StrictFloatCollator<T> {}
+
+float_collator!(f32, u32);
+float_collator!(f64, u64);
+
+/// The tolerance within which an [`ApproxCollator`] treats values as equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Tolerance {
+    /// Values in the same interval `[n * epsilon, (n + 1) * epsilon)` are equal
+    Absolute(f64),
+
+    /// Values with the same sign in the same interval `[b^n, b^(n + 1))`,
+    /// where `b = 1 + epsilon`, are equal
+    Relative(f64),
+}
+
+impl Tolerance {
+    fn epsilon(self) -> f64 {
+        match self {
+            Self::Absolute(epsilon) | Self::Relative(epsilon) => epsilon,
+        }
+    }
+}
+
+/// The error returned by [`ApproxCollator::try_new`] when the epsilon of a [`Tolerance`]
+/// is not finite and positive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ToleranceError(pub f64);
+
+impl fmt::Display for ToleranceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid tolerance: {}", self.0)
+    }
+}
+
+impl std::error::Error for ToleranceError {}
+
+/// A collator for floating-point values which treats values within a [`Tolerance`]
+/// of each other as equal, for example to deduplicate noisy sensor readings.
+///
+/// In order to remain transitive, this collator divides the number line into buckets of
+/// the given tolerance and collates values by bucket. This means that two values which
+/// are closer than the tolerance can still be unequal if they lie on either side of a
+/// bucket boundary. NaN values are equal to each other and collate after all other values.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{ApproxCollator, Collate};
+///
+/// let collator = ApproxCollator::<f64>::absolute(0.1);
+/// assert_eq!(collator.cmp(&1.01, &1.09), Ordering::Equal);
+/// assert_eq!(collator.cmp(&1.01, &1.11), Ordering::Less);
+///
+/// let collator = ApproxCollator::<f64>::relative(0.01);
+/// assert_eq!(collator.cmp(&1000.0, &1001.0), Ordering::Equal);
+/// assert_eq!(collator.cmp(&-1000.0, &1000.0), Ordering::Less);
+/// ```
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound = "", try_from = "ApproxCollatorState")
+)]
+pub struct ApproxCollator<T> {
+    tolerance: Tolerance,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<T>,
+}
+
+// the serialized form of an `ApproxCollator`, which is validated when it's deserialized
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ApproxCollatorState {
+    tolerance: Tolerance,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<ApproxCollatorState> for ApproxCollator<T> {
+    type Error = ToleranceError;
+
+    fn try_from(state: ApproxCollatorState) -> Result<Self, Self::Error> {
+        Self::try_new(state.tolerance)
+    }
+}
+
+impl<T> ApproxCollator<T> {
+    /// Construct a new [`ApproxCollator`] with the given [`Tolerance`].
+    ///
+    /// Panics if the tolerance is not finite and positive.
+    pub fn new(tolerance: Tolerance) -> Self {
+        match Self::try_new(tolerance) {
+            Ok(collator) => collator,
+            Err(cause) => panic!("{cause}"),
+        }
+    }
+
+    /// Construct a new [`ApproxCollator`] with the given [`Tolerance`],
+    /// or return a [`ToleranceError`] if the tolerance is not finite and positive.
+    pub fn try_new(tolerance: Tolerance) -> Result<Self, ToleranceError> {
+        let epsilon = tolerance.epsilon();
+
+        if epsilon.is_finite() && epsilon > 0. {
+            Ok(Self {
+                tolerance,
+                phantom: PhantomData,
+            })
+        } else {
+            Err(ToleranceError(epsilon))
+        }
+    }
+
+    /// Construct a new [`ApproxCollator`] with an absolute tolerance of `epsilon`.
+    pub fn absolute(epsilon: f64) -> Self {
+        Self::new(Tolerance::Absolute(epsilon))
+    }
+
+    /// Construct a new [`ApproxCollator`] with a relative tolerance of `epsilon`.
+    pub fn relative(epsilon: f64) -> Self {
+        Self::new(Tolerance::Relative(epsilon))
+    }
+
+    /// The [`Tolerance`] of this collator.
+    pub fn tolerance(&self) -> Tolerance {
+        self.tolerance
+    }
+
+    fn cmp_f64(&self, left: f64, right: f64) -> Ordering {
+        let (l_class, l_bucket) = self.bucket(left);
+        let (r_class, r_bucket) = self.bucket(right);
+
+        l_class
+            .cmp(&r_class)
+            .then_with(|| l_bucket.total_cmp(&r_bucket))
+    }
+
+    // return a class (negative, zero, positive, or NaN) and a bucket which preserve the order
+    // of the given value, such that values in the same class and bucket are equal
+    fn bucket(&self, value: f64) -> (u8, f64) {
+        if value.is_nan() {
+            return (3, 0.);
+        }
+
+        match self.tolerance {
+            Tolerance::Absolute(epsilon) => {
+                // adding zero normalizes the bucket of a negative zero to a positive zero
+                (1, (value / epsilon).floor() + 0.)
+            }
+            Tolerance::Relative(_) if value == 0. => (1, 0.),
+            Tolerance::Relative(epsilon) => {
+                let bucket = (value.abs().ln() / epsilon.ln_1p()).floor();
+
+                if value < 0. {
+                    (0, -bucket)
+                } else {
+                    (2, bucket)
+                }
+            }
+        }
+    }
+}
+
+impl<T> Clone for ApproxCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ApproxCollator<T> {}
+
+impl<T> PartialEq for ApproxCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tolerance == other.tolerance
+    }
+}
+
+// the tolerance is validated to be finite and positive, so equality is reflexive
+impl<T> Eq for ApproxCollator<T> {}
+
+impl Collate for ApproxCollator<f32> {
+    type Value = f32;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_f64(f64::from(*left), f64::from(*right))
+    }
+}
+
+impl Collate for ApproxCollator<f64> {
+    type Value = f64;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_f64(*left, *right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::check_collate;
+
+    #[test]
+    fn test_float_total_order() {
+        let collator = FloatCollator::<f32>::default();
+
+        let mut values = vec![
+            f32::NAN,
+            1.0,
+            f32::NEG_INFINITY,
+            0.0,
+            -f32::NAN,
+            -0.0,
+            f32::INFINITY,
+            -1.0,
+        ];
+
+        values.sort_by(|l, r| collator.cmp(l, r));
+
+        let expected = [
+            -f32::NAN,
+            f32::NEG_INFINITY,
+            -1.0,
+            -0.0,
+            0.0,
+            1.0,
+            f32::INFINITY,
+            f32::NAN,
+        ];
+
+        let bits = |values: &[f32]| values.iter().map(|v| v.to_bits()).collect::<Vec<u32>>();
+        assert_eq!(bits(&values), bits(&expected));
+    }
+
+    #[test]
+    fn test_nan_policy() {
+        let samples = [f64::NAN, -f64::NAN, f64::NEG_INFINITY, -0.0, 0.0, 1.0];
+
+        let first = FloatCollator::<f64>::new(NanPolicy::First);
+        assert_eq!(check_collate(&first, &samples), Ok(()));
+        assert_eq!(first.cmp(&f64::NAN, &-f64::NAN), Ordering::Equal);
+        assert_eq!(first.cmp(&0.0, &-f64::NAN), Ordering::Greater);
+
+        let last = FloatCollator::<f64>::new(NanPolicy::Last);
+        assert_eq!(check_collate(&last, &samples), Ok(()));
+        assert_eq!(last.cmp(&-f64::NAN, &f64::INFINITY), Ordering::Greater);
+        assert_eq!(last.cmp(&-0.0, &0.0), Ordering::Less);
+
+        let strict = StrictFloatCollator::<f32>::new();
+        assert_eq!(strict.try_cmp(&f32::NAN, &f32::NAN), Err(NanError));
+        assert_eq!(strict.try_cmp(&-0.0, &0.0), Ok(Ordering::Less));
+    }
+
+    #[test]
+    fn test_zeros_and_subnormals() {
+        let subnormal = f64::MIN_POSITIVE / 2.;
+
+        let collator = FloatCollator::<f64>::default();
+        assert_eq!(collator.cmp(&-0.0, &0.0), Ordering::Less);
+        assert_eq!(collator.cmp(&0.0, &subnormal), Ordering::Less);
+
+        let collator = collator.equal_zeros(true);
+        assert_eq!(collator.cmp(&-0.0, &0.0), Ordering::Equal);
+        assert_eq!(collator.cmp(&-subnormal, &0.0), Ordering::Less);
+
+        let collator = collator.flush_subnormals(true);
+        assert_eq!(collator.cmp(&-subnormal, &0.0), Ordering::Equal);
+        assert_eq!(collator.cmp(&subnormal, &f64::MIN_POSITIVE), Ordering::Less);
+
+        let collator = FloatCollator::<f32>::default().flush_subnormals(true);
+        assert_eq!(collator.cmp(&-1e-40, &-0.0), Ordering::Equal);
+        assert_eq!(collator.cmp(&-1e-40, &0.0), Ordering::Less);
+    }
+
+    #[test]
+    fn test_approx_collator() {
+        let samples = [
+            f64::NEG_INFINITY,
+            -1e9,
+            -1.0,
+            -0.99,
+            -0.0,
+            0.0,
+            1e-300,
+            0.05,
+            0.1,
+            0.15,
+            0.99,
+            1.0,
+            1e9,
+            f64::INFINITY,
+            f64::NAN,
+            -f64::NAN,
+        ];
+
+        let absolute = ApproxCollator::<f64>::absolute(0.1);
+        assert_eq!(check_collate(&absolute, &samples), Ok(()));
+        assert_eq!(absolute.cmp(&0.0, &0.05), Ordering::Equal);
+        assert_eq!(absolute.cmp(&-0.0, &0.0), Ordering::Equal);
+        assert_eq!(absolute.cmp(&-0.0, &0.05), Ordering::Equal);
+        assert_eq!(absolute.cmp(&f64::NAN, &-f64::NAN), Ordering::Equal);
+        assert_eq!(absolute.cmp(&f64::INFINITY, &f64::NAN), Ordering::Less);
+
+        let relative = ApproxCollator::<f64>::relative(0.02);
+        assert_eq!(check_collate(&relative, &samples), Ok(()));
+        assert_eq!(relative.cmp(&-1.01, &-1.0), Ordering::Equal);
+        assert_eq!(relative.cmp(&-0.0, &1e-300), Ordering::Less);
+        assert_eq!(relative.cmp(&1.0, &0.99), Ordering::Greater);
+
+        let invalid = Tolerance::Absolute(f64::NAN);
+        assert!(ApproxCollator::<f64>::try_new(invalid).is_err());
+        assert!(ApproxCollator::<f64>::try_new(Tolerance::Relative(0.)).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_approx_collator_serde() {
+        let collator = ApproxCollator::<f64>::relative(0.5);
+        let serialized = serde_json::to_string(&collator).expect("JSON");
+        let deserialized: ApproxCollator<f64> =
+            serde_json::from_str(&serialized).expect("collator");
+        assert_eq!(collator, deserialized);
+
+        let zero = r#"{"tolerance":{"Absolute":0.0}}"#;
+        assert!(serde_json::from_str::<ApproxCollator<f64>>(zero).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// A collator which breaks ties between distinct values which its inner collator finds equal
+/// by comparing their hashes, so that e.g. `merge` preserves distinct values which collate equal
+/// (like strings which differ only by case) instead of dropping one of them.
+///
+/// The order of tied values is arbitrary but deterministic.
+/// Distinct values whose hashes collide still collate equal.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, HashTieBreak};
+///
+/// #[derive(Eq, PartialEq)]
+/// struct CaseInsensitive;
+///
+/// impl Collate for CaseInsensitive {
+///     type Value = String;
+///
+///     fn cmp(&self, left: &String, right: &String) -> Ordering {
+///         left.to_lowercase().cmp(&right.to_lowercase())
+///     }
+/// }
+///
+/// let collator = HashTieBreak::new(CaseInsensitive);
+/// let (upper, lower) = ("ABC".to_string(), "abc".to_string());
+/// assert_ne!(collator.cmp(&upper, &lower), Ordering::Equal);
+/// assert_eq!(collator.cmp(&upper, &lower), collator.cmp(&lower, &upper).reverse());
+/// assert_eq!(collator.cmp(&lower, &"abd".to_string()), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HashTieBreak<C> {
+    collator: C,
+}
+
+impl<C> HashTieBreak<C> {
+    /// Construct a new [`HashTieBreak`] collator.
+    pub fn new(collator: C) -> Self {
+        Self { collator }
+    }
+
+    /// Borrow the underlying collator.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+}
+
+impl<C> Collate for HashTieBreak<C>
+where
+    C: Collate,
+    C::Value: Hash + Eq,
+{
+    type Value = C::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        match self.collator.cmp(left, right) {
+            Ordering::Equal if left != right => hash(0, left).cmp(&hash(0, right)),
+            order => order,
+        }
+    }
+}
+
+/// A collator which defines a deterministic pseudo-random total order of values of type `T`,
+/// determined by a `seed`, e.g. to test that a data structure or stream combinator works with
+/// an arbitrary (but consistent) collation.
+///
+/// Values are collated by a seeded hash, and values whose hashes collide by their natural order.
+/// Unlike the hash of a `HashMap` key, this hash is not randomized, so the same seed produces
+/// the same order every time.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, ShuffleCollator};
+///
+/// let collator = ShuffleCollator::<u32>::new(42);
+///
+/// let mut values = (0..8).collect::<Vec<u32>>();
+/// values.sort_by(|l, r| collator.cmp(l, r));
+/// assert_ne!(values, (0..8).collect::<Vec<u32>>());
+///
+/// let mut reshuffled = values.clone();
+/// reshuffled.reverse();
+/// reshuffled.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(values, reshuffled);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct ShuffleCollator<T> {
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<T>,
+}
+
+impl<T> ShuffleCollator<T> {
+    /// Construct a new [`ShuffleCollator`] with the given `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The seed of this collator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl<T> Default for ShuffleCollator<T> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T> Clone for ShuffleCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ShuffleCollator<T> {}
+
+impl<T> PartialEq for ShuffleCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seed == other.seed
+    }
+}
+
+impl<T> Eq for ShuffleCollator<T> {}
+
+impl<T> fmt::Debug for ShuffleCollator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ShuffleCollator")
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl<T: Hash + Ord> Collate for ShuffleCollator<T> {
+    type Value = T;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        hash(self.seed, left)
+            .cmp(&hash(self.seed, right))
+            .then_with(|| left.cmp(right))
+    }
+}
+
+fn hash<T: Hash + ?Sized>(seed: u64, value: &T) -> u64 {
+    let mut hasher = SeededHasher::new(seed);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// a 64-bit FNV-1a hasher with a SplitMix64 finalizer, which (unlike the `DefaultHasher`)
+// is the same in every Rust release
+struct SeededHasher {
+    state: u64,
+}
+
+impl SeededHasher {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    fn new(seed: u64) -> Self {
+        let mut hasher = Self {
+            state: Self::OFFSET,
+        };
+
+        hasher.write_u64(seed);
+        hasher
+    }
+}
+
+impl Hasher for SeededHasher {
+    fn finish(&self) -> u64 {
+        let mut z = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state = (self.state ^ u64::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_hash_tie_break() {
+        #[derive(Eq, PartialEq)]
+        struct ByLength;
+
+        impl Collate for ByLength {
+            type Value = &'static str;
+
+            fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+                left.len().cmp(&right.len())
+            }
+        }
+
+        let collator = HashTieBreak::new(ByLength);
+
+        let mut values = ["bb", "a", "cc", "b", "aa", "c"];
+        values.sort_by(|l, r| collator.cmp(l, r));
+        assert!(values[..3].iter().all(|value| value.len() == 1));
+
+        for l in values {
+            for r in values {
+                assert_eq!(collator.cmp(&l, &r) == Ordering::Equal, l == r);
+            }
+        }
+
+        // a collator which only finds identical values equal is not affected
+        let descending = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let collator = HashTieBreak::new(descending);
+        assert_eq!(collator.cmp(&1, &2), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_shuffle_collator() {
+        let sorted = |seed| {
+            let collator = ShuffleCollator::<u16>::new(seed);
+            let mut values = (0..100).collect::<Vec<u16>>();
+            values.sort_by(|l, r| collator.cmp(l, r));
+            values
+        };
+
+        let shuffled = sorted(1);
+        assert_eq!(shuffled, sorted(1));
+        assert_ne!(shuffled, sorted(2));
+
+        let mut values = shuffled.clone();
+        values.sort();
+        assert_eq!(values, (0..100).collect::<Vec<u16>>());
+    }
+}
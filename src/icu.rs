@@ -0,0 +1,345 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::Arc;
+
+use icu_collator::{Collator, CollatorOptions};
+use icu_locid::extensions::unicode::{key, value};
+use icu_locid::Locale;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// The level of difference between two strings which an [`Icu4xCollator`] considers significant.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Strength {
+    /// Compare only base letters, ignoring diacritics and case
+    Primary,
+
+    /// Compare base letters and diacritics, ignoring case
+    Secondary,
+
+    /// Compare base letters, diacritics, and case
+    #[default]
+    Tertiary,
+
+    /// Also distinguish punctuation and (e.g. in Japanese) Hiragana from Katakana
+    Quaternary,
+
+    /// Only consider strings equal if they are canonically equivalent
+    Identical,
+}
+
+impl From<Strength> for icu_collator::Strength {
+    fn from(strength: Strength) -> Self {
+        match strength {
+            Strength::Primary => Self::Primary,
+            Strength::Secondary => Self::Secondary,
+            Strength::Tertiary => Self::Tertiary,
+            Strength::Quaternary => Self::Quaternary,
+            Strength::Identical => Self::Identical,
+        }
+    }
+}
+
+/// The order in which an [`Icu4xCollator`] collates Han characters,
+/// which selects a Chinese collation tailoring (the BCP-47 `-u-co-` keyword).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ChineseOrder {
+    /// Collate by Hanyu Pinyin romanization (the default for `zh`)
+    Pinyin,
+
+    /// Collate by total stroke count
+    Stroke,
+
+    /// Collate by Zhuyin (Bopomofo) phonetic symbols
+    Zhuyin,
+
+    /// Collate by radical, then by the number of residual strokes (radical-stroke order)
+    Unihan,
+}
+
+impl ChineseOrder {
+    fn keyword(self) -> icu_locid::extensions::unicode::Value {
+        match self {
+            Self::Pinyin => value!("pinyin"),
+            Self::Stroke => value!("stroke"),
+            Self::Zhuyin => value!("zhuyin"),
+            Self::Unihan => value!("unihan"),
+        }
+    }
+}
+
+/// The options of an [`Icu4xCollator`].
+///
+/// Example:
+/// ```
+/// use collate::{ChineseOrder, Icu4xOptions, Strength};
+///
+/// let options = Icu4xOptions::new(Strength::Tertiary).chinese_order(ChineseOrder::Stroke);
+/// assert_eq!(options.strength, Strength::Tertiary);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Icu4xOptions {
+    /// The level of difference between two strings which is significant
+    pub strength: Strength,
+
+    /// The order of Han characters, or `None` to use the default order of the locale
+    pub chinese_order: Option<ChineseOrder>,
+
+    /// Whether to distinguish Hiragana from Katakana, at the quaternary level
+    pub hiragana_quaternary: bool,
+}
+
+impl Icu4xOptions {
+    /// Construct a new [`Icu4xOptions`] with the given [`Strength`].
+    pub fn new(strength: Strength) -> Self {
+        Self {
+            strength,
+            ..Self::default()
+        }
+    }
+
+    /// Collate Han characters in the given [`ChineseOrder`], regardless of the locale's default.
+    pub fn chinese_order(mut self, order: ChineseOrder) -> Self {
+        self.chinese_order = Some(order);
+        self
+    }
+
+    /// Set whether to distinguish Hiragana from Katakana, e.g. `あ` from `ア` in Japanese.
+    ///
+    /// The Japanese tailoring only distinguishes them at the quaternary level, so this raises
+    /// the strength of the collator to at least [`Strength::Quaternary`].
+    pub fn hiragana_quaternary(mut self, hiragana_quaternary: bool) -> Self {
+        self.hiragana_quaternary = hiragana_quaternary;
+        self
+    }
+
+    fn strength(&self) -> Strength {
+        match self.strength {
+            Strength::Primary | Strength::Secondary | Strength::Tertiary
+                if self.hiragana_quaternary =>
+            {
+                Strength::Quaternary
+            }
+            strength => strength,
+        }
+    }
+}
+
+/// An error encountered while constructing an [`Icu4xCollator`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Icu4xError {
+    /// The given string is not a valid BCP-47 locale identifier
+    InvalidLocale(String),
+
+    /// The collation data for the given locale could not be loaded
+    Unavailable(String),
+}
+
+impl fmt::Display for Icu4xError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLocale(locale) => write!(f, "invalid locale: {locale}"),
+            Self::Unavailable(cause) => write!(f, "collation data unavailable: {cause}"),
+        }
+    }
+}
+
+impl std::error::Error for Icu4xError {}
+
+/// A locale-sensitive collator for strings, implemented in pure Rust using
+/// [`icu_collator`](https://docs.rs/icu_collator) with compiled-in collation data.
+///
+/// Unlike a collator which links ICU4C, this works on targets like `wasm32` and `musl`.
+/// Two [`Icu4xCollator`]s are equal if they have the same locale and [`Icu4xOptions`].
+/// Cloning an [`Icu4xCollator`] is cheap, since the loaded collation data is shared.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CollateRef, Icu4xCollator, Strength};
+///
+/// let collator = Icu4xCollator::new("sv", Strength::Tertiary).expect("collator");
+/// assert_eq!(collator.cmp_ref("z", "ö"), Ordering::Less);
+///
+/// let collator = Icu4xCollator::new("de", Strength::Tertiary).expect("collator");
+/// assert_eq!(collator.cmp_ref("z", "ö"), Ordering::Greater);
+///
+/// let collator = Icu4xCollator::new("en", Strength::Primary).expect("collator");
+/// assert_eq!(collator.cmp_ref("Résumé", "resume"), Ordering::Equal);
+/// ```
+///
+/// Use [`Icu4xOptions`] to select a Chinese or Japanese tailoring explicitly:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{ChineseOrder, CollateRef, Icu4xCollator, Icu4xOptions, Strength};
+///
+/// let options = Icu4xOptions::new(Strength::Tertiary).chinese_order(ChineseOrder::Stroke);
+/// let collator = Icu4xCollator::with_options("zh", options).expect("collator");
+/// assert_eq!(collator.cmp_ref("八", "阿"), Ordering::Less);
+///
+/// let options = Icu4xOptions::new(Strength::Tertiary).hiragana_quaternary(true);
+/// let collator = Icu4xCollator::with_options("ja", options).expect("collator");
+/// assert_eq!(collator.cmp_ref("あ", "ア"), Ordering::Less);
+/// ```
+#[derive(Clone)]
+pub struct Icu4xCollator {
+    locale: Locale,
+    options: Icu4xOptions,
+    collator: Arc<Collator>,
+}
+
+impl Icu4xCollator {
+    /// Construct a new [`Icu4xCollator`] for the given BCP-47 `locale` identifier,
+    /// e.g. `"en-US"` or `"de-u-co-phonebk"`.
+    pub fn new(locale: &str, strength: Strength) -> Result<Self, Icu4xError> {
+        Self::with_options(locale, Icu4xOptions::new(strength))
+    }
+
+    /// Construct a new [`Icu4xCollator`] for the given BCP-47 `locale` identifier
+    /// with the given [`Icu4xOptions`].
+    ///
+    /// A [`ChineseOrder`] overrides any collation keyword in the `locale`.
+    pub fn with_options(locale: &str, options: Icu4xOptions) -> Result<Self, Icu4xError> {
+        let mut locale = locale
+            .parse::<Locale>()
+            .map_err(|_| Icu4xError::InvalidLocale(locale.to_string()))?;
+
+        if let Some(order) = options.chinese_order {
+            locale
+                .extensions
+                .unicode
+                .keywords
+                .set(key!("co"), order.keyword());
+        }
+
+        let mut icu_options = CollatorOptions::new();
+        icu_options.strength = Some(options.strength().into());
+
+        let collator = Collator::try_new(&(&locale).into(), icu_options)
+            .map_err(|cause| Icu4xError::Unavailable(cause.to_string()))?;
+
+        Ok(Self {
+            locale,
+            options,
+            collator: Arc::new(collator),
+        })
+    }
+
+    /// The BCP-47 identifier of the locale of this collator.
+    pub fn locale(&self) -> String {
+        self.locale.to_string()
+    }
+
+    /// The [`Strength`] of this collator.
+    pub fn strength(&self) -> Strength {
+        self.options.strength
+    }
+
+    /// The [`Icu4xOptions`] of this collator.
+    pub fn options(&self) -> Icu4xOptions {
+        self.options
+    }
+}
+
+impl fmt::Debug for Icu4xCollator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Icu4xCollator")
+            .field("locale", &self.locale)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl PartialEq for Icu4xCollator {
+    fn eq(&self, other: &Self) -> bool {
+        self.locale == other.locale && self.options == other.options
+    }
+}
+
+impl Eq for Icu4xCollator {}
+
+impl Collate for Icu4xCollator {
+    type Value = String;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_str(), right.as_str())
+    }
+}
+
+impl CollateRef<str> for Icu4xCollator {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        self.collator.compare(left, right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_icu4x_collator() {
+        assert_send_sync::<Icu4xCollator>();
+
+        assert_eq!(
+            Icu4xCollator::new("not a locale!", Strength::Primary),
+            Err(Icu4xError::InvalidLocale("not a locale!".to_string()))
+        );
+
+        let collator = Icu4xCollator::new("en-US", Strength::Secondary).expect("collator");
+        assert_eq!(collator.locale(), "en-US");
+        assert_eq!(collator.cmp_ref("a", "A"), Ordering::Equal);
+        assert_eq!(collator.cmp_ref("a", "á"), Ordering::Less);
+        assert_eq!(collator.cmp_ref("b", "á"), Ordering::Greater);
+
+        let tertiary = Icu4xCollator::new("en-US", Strength::Tertiary).expect("collator");
+        assert_ne!(collator, tertiary);
+        assert_eq!(tertiary.cmp_ref("a", "A"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_chinese_order() {
+        let collator = |order| {
+            let options = Icu4xOptions::new(Strength::Tertiary).chinese_order(order);
+            Icu4xCollator::with_options("zh", options).expect("collator")
+        };
+
+        // "阿" (ā, 7 strokes, radical 阜) and "八" (bā, 2 strokes, radical 八)
+        let pinyin = collator(ChineseOrder::Pinyin);
+        assert_eq!(pinyin.locale(), "zh-u-co-pinyin");
+        assert_eq!(pinyin.cmp_ref("阿", "八"), Ordering::Less);
+
+        let stroke = collator(ChineseOrder::Stroke);
+        assert_eq!(stroke.cmp_ref("阿", "八"), Ordering::Greater);
+        assert_ne!(pinyin, stroke);
+
+        let unihan = collator(ChineseOrder::Unihan);
+        assert_eq!(unihan.cmp_ref("阿", "八"), Ordering::Greater);
+
+        let zhuyin = collator(ChineseOrder::Zhuyin);
+        assert_eq!(zhuyin.cmp_ref("阿", "八"), Ordering::Greater);
+
+        // an explicit order overrides the collation keyword of the locale
+        let options = Icu4xOptions::new(Strength::Tertiary).chinese_order(ChineseOrder::Pinyin);
+        let collator = Icu4xCollator::with_options("zh-u-co-stroke", options).expect("collator");
+        assert_eq!(collator, pinyin);
+    }
+
+    #[test]
+    fn test_hiragana_quaternary() {
+        let tertiary = Icu4xCollator::new("ja", Strength::Tertiary).expect("collator");
+        assert_eq!(tertiary.cmp_ref("あ", "ア"), Ordering::Equal);
+
+        let options = Icu4xOptions::new(Strength::Tertiary).hiragana_quaternary(true);
+        let collator = Icu4xCollator::with_options("ja", options).expect("collator");
+        assert_eq!(collator.cmp_ref("あ", "ア"), Ordering::Less);
+        assert_eq!(collator.strength(), Strength::Tertiary);
+    }
+}
@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+
+use crate::range::{cmp_start, ends_before, intersects, starts_after, Bounds};
+use crate::{range_is_empty, CollateRef};
+
+/// A map from possibly overlapping ranges of values of type `V` to values of type `T`,
+/// collated by start bound, e.g. for a lock manager or a shard router keyed by key ranges.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, IntervalMap};
+///
+/// let mut locks = IntervalMap::new(Collator::<u32>::default());
+/// locks.insert((Bound::Included(0), Bound::Excluded(10)), "a");
+/// locks.insert((Bound::Included(5), Bound::Excluded(15)), "b");
+/// locks.insert((Bound::Included(20), Bound::Unbounded), "c");
+///
+/// assert_eq!(locks.values_at(&7).collect::<Vec<_>>(), [&"a", &"b"]);
+/// assert_eq!(locks.values_at(&17).count(), 0);
+///
+/// let range = (Bound::Included(12), Bound::Included(20));
+/// assert_eq!(locks.values_overlapping(&range).collect::<Vec<_>>(), [&"b", &"c"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalMap<V, T, C> {
+    collator: C,
+    entries: Vec<(Bounds<V>, T)>,
+}
+
+impl<V, T, C> IntervalMap<V, T, C> {
+    /// Construct a new, empty [`IntervalMap`] which collates its ranges using `collator`.
+    pub fn new(collator: C) -> Self {
+        Self {
+            collator,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Borrow the collator of this [`IntervalMap`].
+    pub fn collator(&self) -> &C {
+        &self.collator
+    }
+
+    /// Return `true` if this [`IntervalMap`] has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the number of entries in this [`IntervalMap`].
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterate over the entries in this [`IntervalMap`] in order of their start bounds.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bounds<V>, &T)> {
+        self.entries.iter().map(|(range, value)| (range, value))
+    }
+
+    /// Retain only the entries for which `predicate` returns `true`, e.g. to release a lock.
+    pub fn retain<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(&Bounds<V>, &T) -> bool,
+    {
+        self.entries
+            .retain(|(range, value)| predicate(range, value))
+    }
+}
+
+impl<V, T, C: CollateRef<V>> IntervalMap<V, T, C> {
+    /// Insert an entry mapping the given `range` to the given `value`.
+    /// Entries whose ranges start at the same bound are kept in order of insertion.
+    pub fn insert(&mut self, range: Bounds<V>, value: T) {
+        let collator = &self.collator;
+
+        let index = self.entries.partition_point(|(r, _)| {
+            cmp_start(collator, r.0.as_ref(), range.0.as_ref()) != Ordering::Greater
+        });
+
+        self.entries.insert(index, (range, value));
+    }
+
+    /// Iterate over the values whose ranges contain the given `point`,
+    /// in order of their start bounds.
+    pub fn values_at<'a>(&'a self, point: &'a V) -> impl Iterator<Item = &'a T> + 'a {
+        let collator = &self.collator;
+
+        // every entry after this index starts after the point
+        let end = self
+            .entries
+            .partition_point(|(r, _)| !starts_after(collator, r.0.as_ref(), point));
+
+        self.entries[..end]
+            .iter()
+            .filter(move |(r, _)| !ends_before(collator, r.1.as_ref(), point))
+            .map(|(_, value)| value)
+    }
+
+    /// Iterate over the values whose ranges have a value in common with the given `range`,
+    /// in order of their start bounds.
+    pub fn values_overlapping<'a>(
+        &'a self,
+        range: &'a Bounds<V>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        let collator = &self.collator;
+
+        // every entry after this index starts after the range ends
+        let end = if range_is_empty(collator, range) {
+            0
+        } else {
+            self.entries
+                .partition_point(|(r, _)| intersects(collator, range.1.as_ref(), r.0.as_ref()))
+        };
+
+        self.entries[..end]
+            .iter()
+            .filter(move |(r, _)| {
+                !range_is_empty(collator, r) && intersects(collator, r.1.as_ref(), range.0.as_ref())
+            })
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_interval_map() {
+        let mut map = IntervalMap::new(Collator::<i32>::default());
+        map.insert((Bound::Unbounded, Bound::Included(0)), 'a');
+        map.insert((Bound::Excluded(0), Bound::Excluded(5)), 'b');
+        map.insert((Bound::Included(3), Bound::Included(3)), 'c');
+        map.insert((Bound::Included(3), Bound::Excluded(3)), 'e');
+        map.insert((Bound::Unbounded, Bound::Unbounded), 'd');
+
+        assert_eq!(map.values_at(&0).collect::<String>(), "ad");
+        assert_eq!(map.values_at(&3).collect::<String>(), "dbc");
+
+        let range = (Bound::Excluded(0), Bound::Excluded(3));
+        assert_eq!(map.values_overlapping(&range).collect::<String>(), "db");
+
+        map.retain(|_, value| *value != 'd');
+        assert_eq!(map.values_at(&5).count(), 0);
+    }
+}
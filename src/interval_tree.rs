@@ -0,0 +1,225 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::range::{cmp_end, cmp_start, ends_before, intersects, starts_after, Bounds};
+use crate::{range_is_empty, CollateRef};
+
+/// An immutable collection of possibly overlapping ranges of values of type `V`, each mapped to
+/// a value of type `T`, which can find every range overlapping a query in `O(log n + k)` time.
+///
+/// This is an augmented sorted `Vec`: the entries are sorted by start bound and treated as an
+/// implicit balanced binary tree, where each node records the greatest end bound in its subtree,
+/// so that a search can skip every subtree which ends before the query starts.
+/// To insert and remove entries, use an [`IntervalMap`](crate::IntervalMap).
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, IntervalTree};
+///
+/// let shards = (0..1000u32).map(|i| ((Bound::Included(i * 10), Bound::Excluded(i * 10 + 15)), i));
+/// let tree = IntervalTree::new(Collator::default(), shards);
+///
+/// assert_eq!(tree.values_at(&42), [&3, &4]);
+///
+/// let range = (Bound::Included(100), Bound::Excluded(120));
+/// assert_eq!(tree.values_overlapping(&range), [&9, &10, &11]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalTree<V, T, C> {
+    collator: C,
+    entries: Vec<(Bounds<V>, T)>,
+    max_end: Vec<usize>,
+}
+
+impl<V, T, C: CollateRef<V>> IntervalTree<V, T, C> {
+    /// Construct a new [`IntervalTree`] from the given `entries`, using `collator`.
+    pub fn new<I>(collator: C, entries: I) -> Self
+    where
+        I: IntoIterator<Item = (Bounds<V>, T)>,
+    {
+        let mut entries = entries
+            .into_iter()
+            .filter(|(range, _)| !range_is_empty(&collator, range))
+            .collect::<Vec<_>>();
+
+        entries.sort_by(|(l, _), (r, _)| cmp_start(&collator, l.0.as_ref(), r.0.as_ref()));
+
+        let mut tree = Self {
+            collator,
+            max_end: vec![0; entries.len()],
+            entries,
+        };
+
+        tree.augment(0, tree.entries.len());
+        tree
+    }
+
+    /// Return the values whose ranges contain the given `point`, in order of their start bounds.
+    pub fn values_at(&self, point: &V) -> Vec<&T> {
+        let collator = &self.collator;
+        let mut values = Vec::new();
+
+        self.search(
+            0,
+            self.entries.len(),
+            &|end| ends_before(collator, end, point),
+            &|range| starts_after(collator, range.0.as_ref(), point),
+            &mut values,
+        );
+
+        values
+    }
+
+    /// Return the values whose ranges have a value in common with the given `range`,
+    /// in order of their start bounds.
+    pub fn values_overlapping(&self, range: &Bounds<V>) -> Vec<&T> {
+        let collator = &self.collator;
+        let mut values = Vec::new();
+
+        if !range_is_empty(collator, range) {
+            self.search(
+                0,
+                self.entries.len(),
+                &|end| !intersects(collator, end, range.0.as_ref()),
+                &|entry| !intersects(collator, range.1.as_ref(), entry.0.as_ref()),
+                &mut values,
+            );
+        }
+
+        values
+    }
+
+    // record the index of the greatest end bound in each subtree, and return that of the root
+    fn augment(&mut self, lo: usize, hi: usize) -> Option<usize> {
+        if lo >= hi {
+            return None;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let mut max = mid;
+
+        for child in [self.augment(lo, mid), self.augment(mid + 1, hi)]
+            .into_iter()
+            .flatten()
+        {
+            let order = cmp_end(
+                &self.collator,
+                self.entries[child].0 .1.as_ref(),
+                self.entries[max].0 .1.as_ref(),
+            );
+
+            if order == Ordering::Greater {
+                max = child;
+            }
+        }
+
+        self.max_end[mid] = max;
+        Some(max)
+    }
+
+    // collect the values in the subtree `lo..hi` which end at or after the query start
+    // (i.e. for which `ends_before` is false) and do not start after the query end
+    fn search<'a, E, S>(
+        &'a self,
+        lo: usize,
+        hi: usize,
+        ends_before: &E,
+        starts_after: &S,
+        values: &mut Vec<&'a T>,
+    ) where
+        E: Fn(Bound<&V>) -> bool,
+        S: Fn(&Bounds<V>) -> bool,
+    {
+        if lo >= hi {
+            return;
+        }
+
+        let mid = lo + (hi - lo) / 2;
+        let max = &self.entries[self.max_end[mid]].0;
+
+        if ends_before(max.1.as_ref()) {
+            // no range in this subtree reaches the query
+            return;
+        }
+
+        self.search(lo, mid, ends_before, starts_after, values);
+
+        let (range, value) = &self.entries[mid];
+        if starts_after(range) {
+            // neither this range nor any range after it reaches the query
+            return;
+        }
+
+        if !ends_before(range.1.as_ref()) {
+            values.push(value);
+        }
+
+        self.search(mid + 1, hi, ends_before, starts_after, values);
+    }
+}
+
+impl<V, T, C> IntervalTree<V, T, C> {
+    /// Return `true` if this [`IntervalTree`] has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Return the number of entries in this [`IntervalTree`].
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Iterate over the entries in this [`IntervalTree`] in order of their start bounds.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bounds<V>, &T)> {
+        self.entries.iter().map(|(range, value)| (range, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, IntervalMap};
+
+    #[test]
+    fn test_interval_tree() {
+        let collator = Collator::<u32>::default();
+
+        // pseudo-random ranges of various widths
+        let entries = (0..500u32)
+            .map(|i| {
+                let start = (i * 7919) % 1000;
+                let end = start + (i * 104729) % 50;
+                let range = match i % 3 {
+                    0 => (Bound::Included(start), Bound::Excluded(end)),
+                    1 => (Bound::Excluded(start), Bound::Included(end)),
+                    _ => (Bound::Included(start), Bound::Included(end)),
+                };
+
+                (range, i)
+            })
+            .collect::<Vec<_>>();
+
+        let mut map = IntervalMap::new(collator);
+        for (range, value) in entries.iter().cloned() {
+            map.insert(range, value);
+        }
+
+        let tree = IntervalTree::new(collator, entries);
+
+        for point in (0..1100).step_by(7) {
+            let mut expected = map.values_at(&point).collect::<Vec<_>>();
+            let mut actual = tree.values_at(&point);
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+
+            let range = (Bound::Excluded(point), Bound::Included(point + 20));
+            let mut expected = map.values_overlapping(&range).collect::<Vec<_>>();
+            let mut actual = tree.values_overlapping(&range);
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected);
+        }
+    }
+}
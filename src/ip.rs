@@ -0,0 +1,102 @@
+use std::cmp::Ordering;
+use std::net::IpAddr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// How an [`IpCollator`] collates IPv4 addresses relative to IPv6 addresses.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IpFamilyOrder {
+    /// Collate an IPv4 address as its IPv4-mapped IPv6 address (e.g. `::ffff:1.2.3.4`)
+    #[default]
+    Mapped,
+
+    /// Collate all IPv4 addresses before all IPv6 addresses
+    V4First,
+}
+
+/// A collator for [`IpAddr`]s which collates IPv4 and IPv6 addresses in a single order.
+///
+/// With [`IpFamilyOrder::Mapped`], an IPv4 address collates immediately before its
+/// IPv4-mapped IPv6 equivalent, so that distinct addresses never collate equal.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::net::IpAddr;
+/// use collate::{Collate, IpCollator, IpFamilyOrder};
+///
+/// let v4: IpAddr = "10.0.0.1".parse().unwrap();
+/// let v6: IpAddr = "::1".parse().unwrap();
+///
+/// assert_eq!(IpCollator::default().cmp(&v4, &v6), Ordering::Greater);
+/// assert_eq!(IpCollator::new(IpFamilyOrder::V4First).cmp(&v4, &v6), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IpCollator {
+    order: IpFamilyOrder,
+}
+
+impl IpCollator {
+    /// Construct a new [`IpCollator`] with the given [`IpFamilyOrder`].
+    pub fn new(order: IpFamilyOrder) -> Self {
+        Self { order }
+    }
+
+    /// The [`IpFamilyOrder`] of this collator.
+    pub fn order(&self) -> IpFamilyOrder {
+        self.order
+    }
+}
+
+impl Collate for IpCollator {
+    type Value = IpAddr;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        match self.order {
+            IpFamilyOrder::Mapped => {
+                let l_addr = match left {
+                    IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+                    IpAddr::V6(addr) => *addr,
+                };
+
+                let r_addr = match right {
+                    IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+                    IpAddr::V6(addr) => *addr,
+                };
+
+                // break ties between an IPv4 address and its IPv4-mapped equivalent
+                l_addr
+                    .cmp(&r_addr)
+                    .then_with(|| left.is_ipv6().cmp(&right.is_ipv6()))
+            }
+            IpFamilyOrder::V4First => left.cmp(right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_ip_collator() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+        let mapped = IpAddr::V6(Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped());
+        let v6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+        let collator = IpCollator::default();
+        assert_eq!(collator.cmp(&v4, &mapped), Ordering::Less);
+        assert_eq!(collator.cmp(&v6, &v4), Ordering::Less);
+        assert_eq!(collator.cmp(&v4, &v4), Ordering::Equal);
+
+        let collator = IpCollator::new(IpFamilyOrder::V4First);
+        assert_eq!(collator.cmp(&v6, &v4), Ordering::Greater);
+        assert_eq!(collator.cmp(&mapped, &v6), Ordering::Greater);
+    }
+}
@@ -0,0 +1,237 @@
+use std::borrow::Borrow;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+
+use crate::CollateRef;
+
+/// Collator-aware equivalents of the [`Iterator`] methods `min_by` and `max_by`,
+/// implemented for every [`Iterator`].
+///
+/// The items of the iterator may be values of type `T` or anything which borrows as `T`
+/// (like `&T`), so these methods return references when iterating over references.
+///
+/// Example:
+/// ```
+/// use collate::{CollateIterExt, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let values = [3, 1, 4, 1, 5];
+///
+/// assert_eq!(values.iter().min_by_collator(&collator), Some(&1));
+/// assert_eq!(values.into_iter().max_by_collator(&collator), Some(5));
+/// assert_eq!(values.iter().minmax_by_collator(&collator), Some((&1, &5)));
+/// ```
+pub trait CollateIterExt: Iterator + Sized {
+    /// Return the first item which collates before or equal to every other item,
+    /// or `None` if the iterator is empty.
+    fn min_by_collator<T, C>(self, collator: &C) -> Option<Self::Item>
+    where
+        Self::Item: Borrow<T>,
+        T: ?Sized,
+        C: CollateRef<T>,
+    {
+        self.min_by(|l, r| collator.cmp_ref(l.borrow(), r.borrow()))
+    }
+
+    /// Return the last item which collates after or equal to every other item,
+    /// or `None` if the iterator is empty.
+    fn max_by_collator<T, C>(self, collator: &C) -> Option<Self::Item>
+    where
+        Self::Item: Borrow<T>,
+        T: ?Sized,
+        C: CollateRef<T>,
+    {
+        self.max_by(|l, r| collator.cmp_ref(l.borrow(), r.borrow()))
+    }
+
+    /// Return both the minimum and the maximum item, as defined by
+    /// [`min_by_collator`](CollateIterExt::min_by_collator) and
+    /// [`max_by_collator`](CollateIterExt::max_by_collator), in a single pass,
+    /// or `None` if the iterator is empty.
+    fn minmax_by_collator<T, C>(mut self, collator: &C) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Borrow<T> + Clone,
+        T: ?Sized,
+        C: CollateRef<T>,
+    {
+        let first = self.next()?;
+        let (mut min, mut max) = (first.clone(), first);
+
+        for item in self {
+            if collator.cmp_ref(item.borrow(), min.borrow()) == Ordering::Less {
+                min = item;
+            } else if collator.cmp_ref(item.borrow(), max.borrow()) != Ordering::Less {
+                max = item;
+            }
+        }
+
+        Some((min, max))
+    }
+}
+
+impl<I: Iterator> CollateIterExt for I {}
+
+/// Return the `k` smallest items of the given `iter` according to `collator`, in collated order,
+/// without sorting the whole input, e.g. for a `LIMIT` query.
+///
+/// Of items which collate equal, those which appear first in `iter` are preferred.
+/// This uses a bounded heap, so it requires `O(k)` memory.
+///
+/// Example:
+/// ```
+/// use collate::{k_largest_by_collator, k_smallest_by_collator, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let values = [5, 1, 4, 2, 3];
+/// assert_eq!(k_smallest_by_collator(values, 2, &collator), [1, 2]);
+/// assert_eq!(k_largest_by_collator(values.iter(), 2, &collator), [&5, &4]);
+/// ```
+pub fn k_smallest_by_collator<I, T, C>(iter: I, k: usize, collator: &C) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // a max-heap of the smallest items seen so far, whose top is the first to be evicted
+    let mut heap = BinaryHeap::with_capacity(k);
+
+    for (index, item) in iter.into_iter().enumerate() {
+        let entry = Entry::new(item, index, collator);
+
+        if heap.len() < k {
+            heap.push(entry);
+        } else if heap.peek().is_some_and(|top| entry < *top) {
+            heap.pop();
+            heap.push(entry);
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|entry| entry.item)
+        .collect()
+}
+
+/// Return the `k` largest items of the given `iter` according to `collator`, in reverse
+/// collated order (i.e. the largest first), without sorting the whole input.
+///
+/// Of items which collate equal, those which appear first in `iter` are preferred.
+/// This uses a bounded heap, so it requires `O(k)` memory.
+pub fn k_largest_by_collator<I, T, C>(iter: I, k: usize, collator: &C) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // a min-heap of the largest items seen so far, whose top is the first to be evicted
+    let mut heap = BinaryHeap::with_capacity(k);
+
+    for (index, item) in iter.into_iter().enumerate() {
+        // reverse the index so that, of equal items, the later item is evicted first
+        let entry = Reverse(Entry::new(item, usize::MAX - index, collator));
+
+        if heap.len() < k {
+            heap.push(entry);
+        } else if heap.peek().is_some_and(|top| entry < *top) {
+            heap.pop();
+            heap.push(entry);
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| entry.item)
+        .collect()
+}
+
+// an item ordered by its collation, then by its position in the input
+struct Entry<'a, I, T: ?Sized, C> {
+    item: I,
+    index: usize,
+    collator: &'a C,
+    phantom: PhantomData<fn(&T)>,
+}
+
+impl<'a, I, T: ?Sized, C> Entry<'a, I, T, C> {
+    fn new(item: I, index: usize, collator: &'a C) -> Self {
+        Self {
+            item,
+            index,
+            collator,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<I: Borrow<T>, T: ?Sized, C: CollateRef<T>> Ord for Entry<'_, I, T, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.collator
+            .cmp_ref(self.item.borrow(), other.item.borrow())
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl<I: Borrow<T>, T: ?Sized, C: CollateRef<T>> PartialOrd for Entry<'_, I, T, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: Borrow<T>, T: ?Sized, C: CollateRef<T>> PartialEq for Entry<'_, I, T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<I: Borrow<T>, T: ?Sized, C: CollateRef<T>> Eq for Entry<'_, I, T, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collate, Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_min_max_by_collator() {
+        let descending = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let collator = descending.map_ref(|pair: &(u32, char)| &pair.0);
+        let pairs = [(2, 'a'), (3, 'b'), (1, 'c'), (3, 'd'), (1, 'e')];
+
+        assert_eq!(pairs.iter().min_by_collator(&collator), Some(&(3, 'b')));
+        assert_eq!(pairs.iter().max_by_collator(&collator), Some(&(1, 'e')));
+
+        let minmax = pairs.into_iter().minmax_by_collator(&collator);
+        assert_eq!(minmax, Some(((3, 'b'), (1, 'e'))));
+
+        let empty = std::iter::empty::<(u32, char)>();
+        assert_eq!(empty.minmax_by_collator(&collator), None);
+    }
+
+    #[test]
+    fn test_k_smallest_and_largest() {
+        let collator = Collator::<u32>::default().map_ref(|pair: &(u32, char)| &pair.0);
+        let pairs = [(2, 'a'), (3, 'b'), (1, 'c'), (3, 'd'), (1, 'e'), (2, 'f')];
+
+        let smallest = k_smallest_by_collator(pairs, 3, &collator);
+        assert_eq!(smallest, [(1, 'c'), (1, 'e'), (2, 'a')]);
+
+        let largest = k_largest_by_collator(pairs, 3, &collator);
+        assert_eq!(largest, [(3, 'b'), (3, 'd'), (2, 'a')]);
+
+        assert_eq!(
+            k_smallest_by_collator(pairs, 10, &collator).len(),
+            pairs.len()
+        );
+        assert!(k_largest_by_collator(pairs, 0, &collator).is_empty());
+    }
+}
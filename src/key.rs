@@ -0,0 +1,422 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::{BytesCollator, BytesOrder, Collate, Collator, DirectedCollator, Direction};
+use crate::{LexCollator, NullOrder, OptionCollator, RowCollator, Then};
+
+/// A collator which can encode each value as a byte string key, such that the lexicographic
+/// (`memcmp`) order of the keys matches the collation of the values, for example to persist
+/// collated data in a key-value store.
+///
+/// Keys are self-delimiting, i.e. no key is a proper prefix of another key of the same collator,
+/// so the keys of several collators can be concatenated to encode a composite key.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, CollateKey, Collator, DirectedCollator, Direction};
+///
+/// let collator = DirectedCollator::new(Collator::<i32>::default(), Direction::Descending);
+///
+/// let mut values = [-3, 7, 0, 12];
+/// values.sort_by(|l, r| collator.cmp(l, r));
+///
+/// let mut keys = values.map(|value| collator.to_key(&value));
+/// keys.sort();
+///
+/// assert_eq!(keys, values.map(|value| collator.to_key(&value)));
+/// ```
+pub trait CollateKey: Collate {
+    /// Append the key of the given `value` to the given `key` buffer.
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>);
+
+    /// Encode the given `value` as a key.
+    fn to_key(&self, value: &Self::Value) -> Vec<u8> {
+        let mut key = Vec::new();
+        self.write_key(value, &mut key);
+        key
+    }
+}
+
+macro_rules! unsigned_key {
+    ($($t:ty),*) => {
+        $(
+            impl CollateKey for Collator<$t> {
+                fn write_key(&self, value: &$t, key: &mut Vec<u8>) {
+                    key.extend_from_slice(&value.to_be_bytes())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed_key {
+    ($($t:ty),*) => {
+        $(
+            impl CollateKey for Collator<$t> {
+                fn write_key(&self, value: &$t, key: &mut Vec<u8>) {
+                    // flip the sign bit so that negative values sort before positive values
+                    let mut bytes = value.to_be_bytes();
+                    bytes[0] ^= 0x80;
+                    key.extend_from_slice(&bytes)
+                }
+            }
+        )*
+    };
+}
+
+unsigned_key!(u8, u16, u32, u64, u128);
+signed_key!(i8, i16, i32, i64, i128);
+
+// the width of a pointer-sized integer depends on the platform, but its keys should not,
+// so a usize or isize is encoded as a u64 or i64
+impl CollateKey for Collator<usize> {
+    fn write_key(&self, value: &usize, key: &mut Vec<u8>) {
+        Collator::<u64>::default().write_key(&(*value as u64), key)
+    }
+}
+
+impl CollateKey for Collator<isize> {
+    fn write_key(&self, value: &isize, key: &mut Vec<u8>) {
+        Collator::<i64>::default().write_key(&(*value as i64), key)
+    }
+}
+
+impl CollateKey for Collator<bool> {
+    fn write_key(&self, value: &bool, key: &mut Vec<u8>) {
+        key.push(u8::from(*value))
+    }
+}
+
+impl CollateKey for Collator<char> {
+    fn write_key(&self, value: &char, key: &mut Vec<u8>) {
+        key.extend_from_slice(&u32::from(*value).to_be_bytes())
+    }
+}
+
+impl CollateKey for Collator<String> {
+    fn write_key(&self, value: &String, key: &mut Vec<u8>) {
+        write_escaped(value.bytes(), key)
+    }
+}
+
+impl CollateKey for Collator<Vec<u8>> {
+    fn write_key(&self, value: &Vec<u8>, key: &mut Vec<u8>) {
+        write_escaped(value.iter().copied(), key)
+    }
+}
+
+impl CollateKey for BytesCollator {
+    fn write_key(&self, value: &Vec<u8>, key: &mut Vec<u8>) {
+        match self.order() {
+            BytesOrder::Memcmp => write_escaped(value.iter().copied(), key),
+            BytesOrder::AsciiCaseInsensitive => {
+                write_escaped(value.iter().map(u8::to_ascii_lowercase), key)
+            }
+            BytesOrder::LengthFirst => {
+                key.extend_from_slice(&(value.len() as u64).to_be_bytes());
+                key.extend_from_slice(value);
+            }
+        }
+    }
+}
+
+impl<C: CollateKey> CollateKey for DirectedCollator<C> {
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+        write_directed(self.inner(), value, self.direction(), key)
+    }
+}
+
+impl<C: CollateKey> CollateKey for OptionCollator<C> {
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+        write_nullable(value.as_ref(), self.nulls(), key, |value, key| {
+            self.inner().write_key(value, key)
+        })
+    }
+}
+
+impl<C: CollateKey> CollateKey for LexCollator<C> {
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+        // precede each element with a continuation byte, so that a prefix collates first
+        for element in value {
+            key.push(1);
+            self.inner().write_key(element, key);
+        }
+
+        key.push(0);
+    }
+}
+
+impl<C1, C2> CollateKey for Then<C1, C2>
+where
+    C1: CollateKey,
+    C2: CollateKey<Value = C1::Value>,
+{
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+        // the first key is self-delimiting, so the second key only breaks ties
+        self.first().write_key(value, key);
+        self.second().write_key(value, key);
+    }
+}
+
+impl<C: CollateKey> CollateKey for RowCollator<C> {
+    fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+        for sort_key in self.keys() {
+            let column = value.get(sort_key.column).and_then(Option::as_ref);
+
+            write_nullable(column, sort_key.nulls, key, |column, key| {
+                write_directed(self.inner(), column, sort_key.direction, key)
+            })
+        }
+    }
+}
+
+// write the key of `value`, inverted if `direction` is descending
+fn write_directed<C: CollateKey>(
+    collator: &C,
+    value: &C::Value,
+    direction: Direction,
+    key: &mut Vec<u8>,
+) {
+    let start = key.len();
+    collator.write_key(value, key);
+
+    if direction == Direction::Descending {
+        // inverting a self-delimiting key reverses its order relative to other keys
+        key[start..].iter_mut().for_each(|byte| *byte = !*byte);
+    }
+}
+
+// write a tag which places a null `value` according to `nulls`, followed by the key of a
+// non-null `value`
+fn write_nullable<T, W>(value: Option<&T>, nulls: NullOrder, key: &mut Vec<u8>, write_value: W)
+where
+    W: FnOnce(&T, &mut Vec<u8>),
+{
+    match (value, nulls) {
+        (None, NullOrder::First) => key.push(0),
+        (None, NullOrder::Last) => key.push(2),
+        (Some(value), _) => {
+            key.push(1);
+            write_value(value, key);
+        }
+    }
+}
+
+/// A value of type `T` whose equality and hash are determined by its [`CollateKey`]
+/// under a collator of type `C`, so that values which collate equal are equal and hash equal,
+/// e.g. to key a `HashMap` consistently with a case-insensitive collator.
+///
+/// Example:
+/// ```
+/// use std::collections::HashMap;
+/// use collate::{BytesCollator, BytesOrder, CollateEq};
+///
+/// let collator = BytesCollator::new(BytesOrder::AsciiCaseInsensitive);
+///
+/// let mut counts = HashMap::new();
+/// for word in ["Apple", "apple", "APPLE", "banana"] {
+///     *counts.entry(CollateEq::new(word.into(), &collator)).or_insert(0) += 1;
+/// }
+///
+/// assert_eq!(counts.len(), 2);
+/// assert_eq!(counts[&CollateEq::new(b"aPpLe".to_vec(), &collator)], 3);
+/// ```
+pub struct CollateEq<T, C> {
+    value: T,
+    key: Vec<u8>,
+    phantom: PhantomData<fn() -> C>,
+}
+
+impl<T, C: CollateKey<Value = T>> CollateEq<T, C> {
+    /// Construct a new [`CollateEq`] by encoding the key of the given `value` using `collator`.
+    pub fn new(value: T, collator: &C) -> Self {
+        let key = collator.to_key(&value);
+
+        Self {
+            value,
+            key,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, C> CollateEq<T, C> {
+    /// Borrow the wrapped value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Borrow the key which determines the equality and hash of this value.
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Destructure this [`CollateEq`] into its wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Clone, C> Clone for CollateEq<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            key: self.key.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, C> PartialEq for CollateEq<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T, C> Eq for CollateEq<T, C> {}
+
+impl<T, C> Hash for CollateEq<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state)
+    }
+}
+
+impl<T: fmt::Debug, C> fmt::Debug for CollateEq<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+// escape each zero byte as 0x00 0xFF and terminate the key with 0x00 0x00,
+// which preserves the order of the bytes and makes the key self-delimiting
+fn write_escaped<I: IntoIterator<Item = u8>>(bytes: I, key: &mut Vec<u8>) {
+    for byte in bytes {
+        key.push(byte);
+
+        if byte == 0 {
+            key.push(0xFF);
+        }
+    }
+
+    key.extend_from_slice(&[0, 0]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FloatCollator, SortKey, TupleCollator};
+
+    fn assert_keys_collated<C: CollateKey>(collator: C, values: &[C::Value]) {
+        for l in values {
+            for r in values {
+                let (l_key, r_key) = (collator.to_key(l), collator.to_key(r));
+                assert_eq!(l_key.cmp(&r_key), collator.cmp(l, r));
+            }
+        }
+    }
+
+    #[test]
+    fn test_collate_key() {
+        assert_keys_collated(Collator::<i16>::default(), &[i16::MIN, -1, 0, 1, i16::MAX]);
+
+        let strings = ["", "\0", "a", "a\0", "a\0\0", "a\u{1}", "ab", "b"].map(String::from);
+        assert_keys_collated(Collator::<String>::default(), &strings);
+
+        let descending =
+            DirectedCollator::new(Collator::<String>::default(), Direction::Descending);
+        assert_keys_collated(descending, &strings);
+
+        let nulls = OptionCollator::nulls_last(Collator::<u8>::default());
+        assert_keys_collated(nulls, &[None, Some(0), Some(255)]);
+
+        let bytes = [b"".to_vec(), b"B".to_vec(), b"a".to_vec(), b"ab".to_vec()];
+        assert_keys_collated(BytesCollator::new(BytesOrder::AsciiCaseInsensitive), &bytes);
+        assert_keys_collated(BytesCollator::new(BytesOrder::LengthFirst), &bytes);
+
+        let floats = [
+            f64::NEG_INFINITY,
+            -1.5,
+            -0.0,
+            0.0,
+            1e-310,
+            2.0,
+            f64::NAN,
+            -f64::NAN,
+        ];
+        assert_keys_collated(FloatCollator::<f64>::default(), &floats);
+
+        let collator = FloatCollator::<f64>::new(crate::NanPolicy::First).equal_zeros(true);
+        assert_keys_collated(collator.flush_subnormals(true), &floats);
+    }
+
+    #[test]
+    fn test_composite_keys() {
+        let elements = DirectedCollator::new(Collator::<u8>::default(), Direction::Descending);
+        let sequences = [
+            vec![],
+            vec![0],
+            vec![0, 0],
+            vec![0, 1],
+            vec![1],
+            vec![255, 0],
+        ];
+        assert_keys_collated(LexCollator::new(elements), &sequences);
+
+        let collator =
+            TupleCollator::new((Collator::<String>::default(), Collator::<i8>::default()));
+        let tuples = [
+            ("", -1),
+            ("", 0),
+            ("a", -128),
+            ("a", 127),
+            ("a\0", 0),
+            ("b", 0),
+        ]
+        .map(|(s, i)| (s.to_string(), i));
+        assert_keys_collated(collator, &tuples);
+
+        let bytes = [
+            b"".to_vec(),
+            b"B".to_vec(),
+            b"a".to_vec(),
+            b"A".to_vec(),
+            b"ab".to_vec(),
+        ];
+        let collator = BytesCollator::new(BytesOrder::AsciiCaseInsensitive)
+            .then(BytesCollator::new(BytesOrder::Memcmp));
+        assert_keys_collated(collator, &bytes);
+
+        let keys = vec![
+            SortKey::new(1, Direction::Descending),
+            SortKey {
+                column: 0,
+                direction: Direction::Ascending,
+                nulls: NullOrder::First,
+            },
+        ];
+        let collator = RowCollator::new(Collator::<u32>::default(), keys);
+        let rows = [
+            vec![],
+            vec![Some(1)],
+            vec![None, Some(30)],
+            vec![Some(1), Some(30)],
+            vec![Some(2), Some(30)],
+            vec![Some(3), Some(40)],
+            vec![Some(2), None],
+        ];
+        assert_keys_collated(collator, &rows);
+    }
+
+    #[test]
+    fn test_collate_eq() {
+        use std::collections::HashSet;
+
+        let collator = FloatCollator::<f64>::default().equal_zeros(true);
+        let zeros = [0.0, -0.0].map(|zero| CollateEq::new(zero, &collator));
+        assert_eq!(zeros[0], zeros[1]);
+
+        let set = zeros.into_iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+    }
+}
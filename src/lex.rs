@@ -0,0 +1,132 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// A collator for sequences of values, which collates them lexicographically
+/// using an element collator, e.g. for composite B-Tree keys.
+///
+/// A sequence which is a prefix of another sequence collates before it.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collator, CollateRef, LexCollator};
+///
+/// let collator = LexCollator::new(Collator::<u32>::default());
+/// assert_eq!(collator.cmp_ref(&[1, 2][..], &[1, 3][..]), Ordering::Less);
+/// assert_eq!(collator.cmp_ref(&[1, 2][..], &[1][..]), Ordering::Greater);
+/// assert_eq!(collator.cmp_ref(&[][..], &[0][..]), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LexCollator<C> {
+    collator: C,
+}
+
+impl<C> LexCollator<C> {
+    /// Construct a new [`LexCollator`] using the given element `collator`.
+    pub fn new(collator: C) -> Self {
+        Self { collator }
+    }
+
+    /// Borrow the element collator.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+}
+
+impl<C: Collate> Collate for LexCollator<C> {
+    type Value = Vec<C::Value>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_slice(), right.as_slice())
+    }
+}
+
+impl<C: Collate> CollateRef<[C::Value]> for LexCollator<C> {
+    fn cmp_ref(&self, left: &[C::Value], right: &[C::Value]) -> Ordering {
+        cmp_slices(&self.collator, left, right)
+    }
+}
+
+/// Compare two slices lexicographically, collating their elements using `collator`.
+///
+/// A slice which is a prefix of another slice collates before it.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{cmp_slices, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// assert_eq!(cmp_slices(&collator, &[1, 2], &[1, 3]), Ordering::Less);
+/// assert_eq!(cmp_slices(&collator, &[1, 2], &[1]), Ordering::Greater);
+/// ```
+pub fn cmp_slices<T, C>(collator: &C, left: &[T], right: &[T]) -> Ordering
+where
+    C: CollateRef<T>,
+{
+    cmp_iters::<_, _, T, C>(collator, left, right)
+}
+
+/// Compare the items of two iterators lexicographically, collating them using `collator`.
+///
+/// An iterator whose items are a prefix of the items of another iterator collates before it.
+/// Neither iterator is advanced past the first item which collates unequal.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{cmp_iters, Collator};
+///
+/// let collator = Collator::<String>::default();
+/// let left = "usr/local/bin".split('/');
+/// let right = "usr/local".split('/');
+/// assert_eq!(cmp_iters::<_, _, str, _>(&collator, left, right), Ordering::Greater);
+/// ```
+pub fn cmp_iters<L, R, T, C>(collator: &C, left: L, right: R) -> Ordering
+where
+    L: IntoIterator,
+    L::Item: Borrow<T>,
+    R: IntoIterator,
+    R::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+
+    loop {
+        match (left.next(), right.next()) {
+            (Some(l), Some(r)) => match collator.cmp_ref(l.borrow(), r.borrow()) {
+                Ordering::Equal => {}
+                order => return order,
+            },
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_lex_collator() {
+        let descending = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let collator = LexCollator::new(descending);
+
+        let mut keys = vec![vec![1, 2], vec![2], vec![1], vec![2, 0], vec![]];
+        keys.sort_by(|l, r| collator.cmp(l, r));
+
+        // elements collate in descending order, but a prefix still collates first
+        assert_eq!(keys, vec![vec![], vec![2], vec![2, 0], vec![1], vec![1, 2]]);
+    }
+}
@@ -8,31 +8,536 @@
 //!
 //! Use the "stream" feature flag to enable `diff` and `try_diff` functions to compute the
 //! difference between two collated `Stream`s, and the `merge` and `try_merge` functions
-//! to merge two collated `Stream`s.
+//! to merge two collated `Stream`s, or `merge_many` and `try_merge_many` to merge any number
+//! of collated `Stream`s (which can be checkpointed and resumed with `merge_many_resume`).
+//! The `merge_latest` and `try_merge_latest` functions merge
+//! two collated `Stream`s of versioned records, keeping only the latest version of each key,
+//! and the `merge_kv` and `try_merge_kv` functions merge two collated `Stream`s of key-value
+//! pairs, combining the values of equal keys. The `anti_join` and `semi_join` functions
+//! (and their `try_` equivalents) filter a collated `Stream` of key-value pairs by the presence
+//! of each key in a collated `Stream` of keys. The `try_collate_merge` and `try_collate_diff`
+//! functions accept a fallible [`TryCollate`] collator and yield its errors in the output stream,
+//! and the `async_merge` and `async_diff` functions await the comparisons of an [`AsyncCollate`]
+//! collator. The `expect_collated` function checks that a `Stream` is collated in a debug build.
+//!
+//! Use [`bisect_left`], [`bisect_right`], [`bisect_range`], [`binary_search_by_collator`], and
+//! [`partition_point_by_collator`] to search a sorted slice using a collator
+//! (or [`slice_range`] to borrow the elements of a sorted slice within a range),
+//! and [`is_sorted_by_collator`] (or [`debug_assert_sorted_by_collator`] or [`assert_collated`])
+//! to check that an input is actually collated. Use [`merge_vecs`] or [`merge_into`] to merge
+//! collated `Vec`s which are already in memory, and the [`CollateIterExt`] trait to find the
+//! minimum or maximum item of an iterator using a collator, or [`k_smallest_by_collator`] and
+//! [`k_largest_by_collator`] to select the first or last `k` items without a full sort.
+//! Use [`ranks_by_collator`] to rank the elements of a slice in a given [`RankMode`],
+//! and [`group_equal_by_collator`] to split a collated slice into groups of equal elements.
+//! Use [`select_nth_by_collator`] or [`quantiles_by_collator`] to find the median or other
+//! quantiles of a slice without sorting it.
+//!
+//! A collator for a discrete type which implements [`Successor`] and [`Predecessor`] can compute
+//! the value which immediately follows or precedes a given value, e.g. to convert an excluded
+//! range bound to an included one (or to put a range in a canonical form with
+//! [`normalize_range`]), or to iterate over a range using [`iter_range`].
+//!
+//! A collator which implements [`CollateKey`] can encode values as byte string keys whose
+//! lexicographic order matches its collation, e.g. to persist them in a key-value store
+//! (including the composite collators [`LexCollator`], [`TupleCollator`], [`Then`], and
+//! [`RowCollator`], whose keys concatenate the keys of their parts),
+//! and a [`CollateEq`] uses that key to hash values consistently with their collation.
+//!
+//! A [`LexCollator`] collates sequences of values lexicographically using an element collator
+//! (or use [`cmp_slices`] or [`cmp_iters`] to compare two sequences directly),
+//! and a [`TupleCollator`] collates tuples using a separate collator for each field.
+//!
+//! A [`DynCollator`] collates [`Dynamic`] values of mixed types according to a configurable
+//! type precedence, e.g. for databases with dynamically typed columns.
+//!
+//! Use [`Collate::then`] to break ties between values which one collator finds equal using
+//! another collator, e.g. to collate by last name and then by first name, or a [`HashTieBreak`]
+//! to break ties between distinct values by hash, so that merging them does not drop any.
+//! Use [`Collate::map_ref`] to collate a wrapper type by a field which it contains
+//! (or the [`delegate_collate`] macro to implement a newtype collator which does the same),
+//! and [`DynCollate`] to store a collator whose type is only known at runtime as a trait object.
+//!
+//! A [`FloatCollator`] collates `f32` and `f64` values according to the IEEE 754 total order
+//! (or a [`StrictFloatCollator`] rejects NaN values using the fallible [`TryCollate`] trait),
+//! an [`ApproxCollator`] collates them within a [`Tolerance`],
+//! a [`BytesCollator`] collates byte strings in a selectable [`BytesOrder`],
+//! an [`IpCollator`] collates IPv4 and IPv6 addresses in a single order,
+//! a [`PathCollator`] collates file system paths component by component,
+//! and a [`NaturalCollator`] collates strings with embedded numbers in numeric order.
+//!
+//! Use [`overlapping_indices`] (or [`first_overlapping`], [`overlaps_any`], or [`overlaps_all`])
+//! to find which of a collated list of disjoint ranges overlap a query range,
+//! [`range_union`] (or [`discrete_range_union`]) to merge two overlapping or adjacent ranges,
+//! [`range_difference`] to subtract one range from another, and [`range_gap`] to find the range
+//! between two disjoint ranges. Use [`coalesce_ranges`] to merge a sorted sequence of ranges
+//! into disjoint ranges. A [`RangeSet`] maintains
+//! a collated set of disjoint ranges, coalescing them as they're inserted, and an [`IntervalMap`]
+//! maps possibly overlapping ranges to values (or, for bulk queries over many ranges,
+//! an [`IntervalTree`]). An [`NDRange`] is a multi-dimensional range with a range along each
+//! axis, e.g. to select a slice of a tensor.
+//!
+//! A [`MergedView`] overlays a collated base slice with a collated [`Delta`], for example to
+//! implement a copy-on-write transactional view of a collated collection.
+//!
+//! A [`TableCollator`] collates a finite set of values in a custom order defined at runtime,
+//! placing unknown values according to an [`UnknownValue`] policy,
+//! while a [`StrictTableCollator`] rejects unknown values using the [`TryCollate`] trait.
+//!
+//! A [`PermutationCollator`] collates integer keys according to a permutation or a ranking
+//! function, e.g. by an externally computed popularity rank.
+//!
+//! A [`ShuffleCollator`] collates values in a pseudo-random order determined by a seed,
+//! for testing code against an arbitrary but consistent collation.
+//!
+//! An [`OptionCollator`] collates optional values, placing `None` first or last according to
+//! a [`NullOrder`], and a [`ResultCollator`] collates `Result`s, placing `Err` values first or
+//! last according to an [`ErrorOrder`]. A [`RowCollator`] collates rows of nullable values
+//! according to a list of [`SortKey`]s, which can be parsed from a textual sort specification
+//! using [`parse_sort_spec`]. A [`CompositeCollator`] collates records of any type according to
+//! a list of keys assembled at runtime.
+//!
+//! Use the "casefold" feature flag to enable the `CaseFoldCollator`, which matches strings
+//! case-insensitively with locale-specific presets (like Turkish dotted and dotless "i")
+//! without the weight of a full ICU dependency.
+//!
+//! Use the "chrono" feature flag to enable the `DateTimeCollator`, which collates date-times
+//! by instant regardless of their time zone.
+//!
+//! Use the "complex" feature flag to enable the `ComplexCollator`, which collates complex numbers
+//! by norm, lexicographically, or by argument.
+//!
+//! Use the "icu" feature flag to enable the `Icu4xCollator`, a locale-sensitive string collator
+//! implemented in pure Rust, which works on targets where linking ICU4C is impractical.
+//! Its `Icu4xOptions` select a Chinese order (pinyin, stroke count, zhuyin, or radical-stroke)
+//! and whether to distinguish Hiragana from Katakana in Japanese.
+//!
+//! Use the "io" feature flag to enable decoding collated records from an `AsyncRead` source,
+//! and the `merge_readers` function to merge the records of many sorted files.
+//!
+//! Use the "lru" feature flag to enable the `CachedKeyCollator`, which collates values by an
+//! expensive sort key, computing the sort key of each value at most once while it's cached.
+//!
+//! Use the "num" feature flag to enable collators for arbitrary-precision numbers, like
+//! `BigIntCollator`, which can also compare them to primitive integers.
+//!
+//! Use the "rayon" feature flag to enable parallel equivalents of the sorting helpers,
+//! like `par_argsort_by_collator`.
+//!
+//! Use the "search" feature flag to enable the `SearchCollator`, which ignores case and
+//! diacritics in order to build lookup indices.
+//!
+//! Use the "semver" feature flag to enable the `VersionCollator`, which collates semantic
+//! versions according to their precedence.
+//!
+//! Use the "serde" feature flag to enable serialization of collators, so that a collation
+//! configuration (or a `MergeCheckpoint`) can be persisted and reconstructed.
 
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::ops::{
     Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
 };
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "stream")]
 pub use stream::*;
 
+pub use alphabet::*;
+pub use bisect::*;
+pub use bytes::*;
+#[cfg(feature = "lru")]
+pub use cached::*;
+#[cfg(feature = "casefold")]
+pub use casefold::*;
+#[cfg(feature = "complex")]
+pub use complex::*;
+pub use composite::*;
+#[cfg(feature = "chrono")]
+pub use datetime::*;
+pub use directed::*;
+pub use dynamic::*;
+pub use float::*;
+pub use hash::*;
+#[cfg(feature = "icu")]
+pub use icu::*;
+pub use interval::*;
+pub use interval_tree::*;
+pub use ip::*;
+pub use iter::*;
+pub use key::*;
+pub use lex::*;
+pub use map::*;
+pub use merge::*;
+pub use natural::*;
+pub use ndrange::*;
+#[cfg(feature = "num")]
+pub use num::*;
+pub use option::*;
+pub use overlap::*;
+pub use path::*;
+pub use pattern::*;
+pub use permutation::*;
+pub use prefix::*;
+pub use range::*;
+pub use range_set::*;
+pub use result::*;
+pub use row::*;
+#[cfg(feature = "search")]
+pub use search::*;
+pub use sort::*;
+pub use successor::*;
+pub use table::*;
+pub use then::*;
+pub use tuple::*;
+#[cfg(feature = "semver")]
+pub use version::*;
+pub use view::*;
+
+pub mod testing;
+
+mod alphabet;
+mod bisect;
+mod bytes;
+#[cfg(feature = "lru")]
+mod cached;
+#[cfg(feature = "casefold")]
+mod casefold;
+#[cfg(feature = "complex")]
+mod complex;
+mod composite;
+#[cfg(feature = "chrono")]
+mod datetime;
+mod delegate;
+mod directed;
+mod dynamic;
+mod float;
+mod hash;
+#[cfg(feature = "icu")]
+mod icu;
+mod interval;
+mod interval_tree;
+mod ip;
+mod iter;
+mod key;
+mod lex;
+mod map;
+mod merge;
+mod natural;
+mod ndrange;
+#[cfg(feature = "num")]
+mod num;
+mod option;
+mod overlap;
+mod path;
+mod pattern;
+mod permutation;
+mod prefix;
+mod range;
+mod range_set;
+mod result;
+mod row;
+#[cfg(feature = "search")]
+mod search;
+mod sort;
 #[cfg(feature = "stream")]
 mod stream;
+mod successor;
+mod table;
+mod then;
+mod tuple;
+#[cfg(feature = "semver")]
+mod version;
+mod view;
 
 /// A collator for type `Value`.
+///
+/// A reference, [`Box`], or [`Arc`] of a collator is also a collator for the same type,
+/// so that a collator with heavyweight state can be shared without cloning it.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::sync::Arc;
+/// use collate::{Collate, CollateRef, Collator};
+///
+/// fn collate<C: CollateRef<u32>>(collator: C, left: u32, right: u32) -> Ordering {
+///     collator.cmp_ref(&left, &right)
+/// }
+///
+/// let collator = Arc::new(Collator::<u32>::default());
+/// assert_eq!(collate(&collator, 1, 2), Ordering::Less);
+/// assert_eq!(collate(Arc::clone(&collator), 2, 2), Ordering::Equal);
+/// assert_eq!(collate(Box::new(*collator), 3, 2), Ordering::Greater);
+///
+/// let collator = Collator::<String>::default();
+/// let (a, b, c) = ("a".to_string(), "b".to_string(), "c".to_string());
+/// assert_eq!(collator.max_ref(&a, &b), &b);
+/// assert_eq!(collator.clamp_ref(&a, &b, &c), &b);
+/// assert_eq!(collator.min(c, a), "a");
+/// ```
 pub trait Collate: Sized + Eq {
     type Value;
 
     /// Return the collation of the `left` value relative to the `right` value.
     fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering;
+
+    /// Return the lesser of `left` and `right`, or `left` if they are equal.
+    fn min(&self, left: Self::Value, right: Self::Value) -> Self::Value {
+        match self.cmp(&left, &right) {
+            Ordering::Greater => right,
+            _ => left,
+        }
+    }
+
+    /// Return the greater of `left` and `right`, or `right` if they are equal.
+    fn max(&self, left: Self::Value, right: Self::Value) -> Self::Value {
+        match self.cmp(&left, &right) {
+            Ordering::Greater => left,
+            _ => right,
+        }
+    }
+
+    /// Restrict `value` to the interval `[low, high]`.
+    ///
+    /// Panics: if `low` collates after `high`.
+    fn clamp(&self, value: Self::Value, low: Self::Value, high: Self::Value) -> Self::Value {
+        assert_ne!(
+            self.cmp(&low, &high),
+            Ordering::Greater,
+            "invalid clamp bounds"
+        );
+
+        if self.cmp(&value, &low) == Ordering::Less {
+            low
+        } else if self.cmp(&value, &high) == Ordering::Greater {
+            high
+        } else {
+            value
+        }
+    }
+
+    /// Borrow the lesser of `left` and `right`, or `left` if they are equal.
+    fn min_ref<'a>(&self, left: &'a Self::Value, right: &'a Self::Value) -> &'a Self::Value {
+        match self.cmp(left, right) {
+            Ordering::Greater => right,
+            _ => left,
+        }
+    }
+
+    /// Borrow the greater of `left` and `right`, or `right` if they are equal.
+    fn max_ref<'a>(&self, left: &'a Self::Value, right: &'a Self::Value) -> &'a Self::Value {
+        match self.cmp(left, right) {
+            Ordering::Greater => left,
+            _ => right,
+        }
+    }
+
+    /// Borrow `value` restricted to the interval `[low, high]`.
+    ///
+    /// Panics: if `low` collates after `high`.
+    fn clamp_ref<'a>(
+        &self,
+        value: &'a Self::Value,
+        low: &'a Self::Value,
+        high: &'a Self::Value,
+    ) -> &'a Self::Value {
+        assert_ne!(
+            self.cmp(low, high),
+            Ordering::Greater,
+            "invalid clamp bounds"
+        );
+
+        if self.cmp(value, low) == Ordering::Less {
+            low
+        } else if self.cmp(value, high) == Ordering::Greater {
+            high
+        } else {
+            value
+        }
+    }
+
+    /// Return the collation of each value in `lefts` relative to the value at the same index
+    /// in `rights`, e.g. to compare two columns of a columnar batch.
+    ///
+    /// The default implementation calls [`Collate::cmp`] on each pair. A collator can override it
+    /// to compare a whole batch more efficiently, e.g. by computing each sort key only once.
+    ///
+    /// Panics: if `lefts` and `rights` have different lengths.
+    ///
+    /// Example:
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use collate::{Collate, Collator};
+    ///
+    /// let collator = Collator::<u32>::default();
+    /// assert_eq!(
+    ///     collator.cmp_batch(&[1, 2, 3], &[2, 2, 2]),
+    ///     [Ordering::Less, Ordering::Equal, Ordering::Greater]
+    /// );
+    /// ```
+    fn cmp_batch(&self, lefts: &[Self::Value], rights: &[Self::Value]) -> Vec<Ordering> {
+        assert_eq!(lefts.len(), rights.len(), "batch length");
+
+        lefts
+            .iter()
+            .zip(rights)
+            .map(|(left, right)| self.cmp(left, right))
+            .collect()
+    }
+
+    /// Construct a [`MapRef`] collator for values of type `U` which collates the field
+    /// borrowed from each value by `project` using this collator.
+    fn map_ref<U, F>(self, project: F) -> MapRef<Self, F, U>
+    where
+        F: Fn(&U) -> &Self::Value,
+    {
+        MapRef::new(self, project)
+    }
+
+    /// Construct a [`Then`] collator which collates values using this collator,
+    /// falling back to the `other` collator when this collator finds two values equal.
+    fn then<O>(self, other: O) -> Then<Self, O>
+    where
+        O: Collate<Value = Self::Value>,
+    {
+        Then::new(self, other)
+    }
+}
+
+impl<C: Collate> Collate for &C {
+    type Value = C::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        (**self).cmp(left, right)
+    }
+
+    fn cmp_batch(&self, lefts: &[Self::Value], rights: &[Self::Value]) -> Vec<Ordering> {
+        (**self).cmp_batch(lefts, rights)
+    }
+}
+
+impl<C: Collate> Collate for Box<C> {
+    type Value = C::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        (**self).cmp(left, right)
+    }
+
+    fn cmp_batch(&self, lefts: &[Self::Value], rights: &[Self::Value]) -> Vec<Ordering> {
+        (**self).cmp_batch(lefts, rights)
+    }
 }
 
+impl<C: Collate> Collate for Arc<C> {
+    type Value = C::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        (**self).cmp(left, right)
+    }
+
+    fn cmp_batch(&self, lefts: &[Self::Value], rights: &[Self::Value]) -> Vec<Ordering> {
+        (**self).cmp_batch(lefts, rights)
+    }
+}
+
+/// A fallible collator for type `Value`, for collations which are not defined for every value.
+pub trait TryCollate: Sized + Eq {
+    type Value;
+    type Error: std::error::Error;
+
+    /// Return the collation of the `left` value relative to the `right` value,
+    /// or an error if they cannot be collated.
+    fn try_cmp(&self, left: &Self::Value, right: &Self::Value) -> Result<Ordering, Self::Error>;
+}
+
+/// A collator for type `Value` whose comparisons require asynchronous I/O,
+/// for example because the values are large and stored in a block cache.
+///
+/// Use the "stream" feature flag to enable the `async_merge` and `async_diff` functions,
+/// which await each comparison.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::future::Future;
+/// use collate::AsyncCollate;
+///
+/// #[derive(Eq, PartialEq)]
+/// struct BlockCollator;
+///
+/// impl AsyncCollate for BlockCollator {
+///     type Value = u64;
+///
+///     fn cmp(&self, left: &u64, right: &u64) -> impl Future<Output = Ordering> + Send {
+///         // a real implementation would load the blocks identified by `left` and `right`
+///         let order = left.cmp(right);
+///         async move { order }
+///     }
+/// }
+/// ```
+pub trait AsyncCollate: Sized + Eq {
+    type Value;
+
+    /// Return the collation of the `left` value relative to the `right` value.
+    fn cmp(&self, left: &Self::Value, right: &Self::Value)
+        -> impl Future<Output = Ordering> + Send;
+}
+
+/// A collator for references to type `T`.
+///
+/// This is implemented for every [`Collate`] type for its own `Value`, and provides predicates
+/// like [`CollateRef::is_lt`] so that call sites need not match on an [`Ordering`].
+/// (These are not named `eq`, `lt`, etc. to avoid any ambiguity with [`PartialEq`]
+/// and [`PartialOrd`].)
+///
+/// Example:
+/// ```
+/// use collate::{CollateRef, Collator};
+///
+/// let collator = Collator::<String>::default();
+/// assert!(collator.is_lt("a", "b"));
+/// assert!(collator.is_ge("b", "b"));
+/// assert!(collator.is_ne(&"a".to_string(), &"b".to_string()));
+/// ```
 pub trait CollateRef<T: ?Sized>: Collate {
     /// Return the collation of the `left` reference relative to the `right` reference.
     fn cmp_ref(&self, left: &T, right: &T) -> Ordering;
+
+    /// Return `true` if `left` collates equal to `right`.
+    fn is_eq(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) == Ordering::Equal
+    }
+
+    /// Return `true` if `left` does not collate equal to `right`.
+    fn is_ne(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) != Ordering::Equal
+    }
+
+    /// Return `true` if `left` collates before `right`.
+    fn is_lt(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) == Ordering::Less
+    }
+
+    /// Return `true` if `left` collates before or equal to `right`.
+    fn is_le(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) != Ordering::Greater
+    }
+
+    /// Return `true` if `left` collates after `right`.
+    fn is_gt(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) == Ordering::Greater
+    }
+
+    /// Return `true` if `left` collates after or equal to `right`.
+    fn is_ge(&self, left: &T, right: &T) -> bool {
+        self.cmp_ref(left, right) != Ordering::Less
+    }
 }
 
 impl<C: Collate> CollateRef<C::Value> for C {
@@ -41,8 +546,120 @@ impl<C: Collate> CollateRef<C::Value> for C {
     }
 }
 
+/// Collation of a stored key of type `K` relative to a query of type `Q`,
+/// analogous to the use of [`Borrow`] by `BTreeMap::get`.
+///
+/// This is implemented for any [`CollateRef<Q>`] where `K: Borrow<Q>`,
+/// so that a collection of keys can be queried without allocating a new key.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, CollateEquivalent, CollateRef};
+///
+/// #[derive(Eq, PartialEq)]
+/// struct Ascii;
+///
+/// impl Collate for Ascii {
+///     type Value = String;
+///
+///     fn cmp(&self, left: &String, right: &String) -> Ordering {
+///         self.cmp_ref(left.as_str(), right.as_str())
+///     }
+/// }
+///
+/// impl CollateRef<str> for Ascii {
+///     fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+///         left.to_ascii_lowercase().cmp(&right.to_ascii_lowercase())
+///     }
+/// }
+///
+/// let key = "Hello".to_string();
+/// assert_eq!(Ascii.cmp_equivalent(&key, "HELLO"), Ordering::Equal);
+/// assert_eq!(Ascii.cmp_equivalent(&key, "world"), Ordering::Less);
+/// ```
+pub trait CollateEquivalent<K: ?Sized, Q: ?Sized> {
+    /// Return the collation of the stored `key` relative to the given `query`.
+    fn cmp_equivalent(&self, key: &K, query: &Q) -> Ordering;
+}
+
+impl<C, K, Q> CollateEquivalent<K, Q> for C
+where
+    C: CollateRef<Q>,
+    K: Borrow<Q> + ?Sized,
+    Q: ?Sized,
+{
+    fn cmp_equivalent(&self, key: &K, query: &Q) -> Ordering {
+        self.cmp_ref(key.borrow(), query)
+    }
+}
+
+/// An object-safe form of [`CollateRef<T>`], so that a collator whose type is only known at
+/// runtime can be stored as a `Box<dyn DynCollate<T>>` or `Arc<dyn DynCollate<T> + Send + Sync>`.
+///
+/// This is implemented for every [`CollateRef<T>`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::sync::Arc;
+/// use collate::{Collator, Direction, DirectedCollator, DynCollate};
+///
+/// fn collator(descending: bool) -> Arc<dyn DynCollate<u32> + Send + Sync> {
+///     if descending {
+///         Arc::new(DirectedCollator::new(Collator::default(), Direction::Descending))
+///     } else {
+///         Arc::new(Collator::default())
+///     }
+/// }
+///
+/// let mut column = [2, 3, 1];
+/// let collator = collator(true);
+/// column.sort_by(|l, r| collator.dyn_cmp(l, r));
+/// assert_eq!(column, [3, 2, 1]);
+/// assert_eq!(collator.dyn_cmp(&1, &2), Ordering::Greater);
+/// ```
+pub trait DynCollate<T: ?Sized> {
+    /// Return the collation of the `left` reference relative to the `right` reference.
+    fn dyn_cmp(&self, left: &T, right: &T) -> Ordering;
+}
+
+impl<T, C> DynCollate<T> for C
+where
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    fn dyn_cmp(&self, left: &T, right: &T) -> Ordering {
+        self.cmp_ref(left, right)
+    }
+}
+
 /// A generic collator for any type `T: Ord`.
+///
+/// A `Collator<T>` can also collate the borrowed form of a standard library type `T`,
+/// like `str` for `String`, `[T]` for `Vec<T>`, or `Path` for `PathBuf`,
+/// so that e.g. a collection of keys can be queried using [`CollateEquivalent`] (like
+/// `BTreeMap::get`) or a range can be checked without allocating its bounds.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use std::path::{Path, PathBuf};
+/// use collate::{CollateEquivalent, CollateRef, Collator};
+///
+/// let collator = Collator::<String>::default();
+/// assert_eq!(collator.cmp_ref("apple", "banana"), Ordering::Less);
+/// assert_eq!(collator.cmp_equivalent(&"cherry".to_string(), "banana"), Ordering::Greater);
+///
+/// let collator = Collator::<Vec<u8>>::default();
+/// assert_eq!(collator.cmp_ref(&[1, 2][..], &[1, 2, 3][..]), Ordering::Less);
+///
+/// let collator = Collator::<PathBuf>::default();
+/// assert_eq!(collator.cmp_equivalent(&PathBuf::from("b"), Path::new("a")), Ordering::Greater);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
 pub struct Collator<T> {
+    #[cfg_attr(feature = "serde", serde(skip))]
     phantom: PhantomData<T>,
 }
 
@@ -56,9 +673,7 @@ impl<T> Default for Collator<T> {
 
 impl<T> Clone for Collator<T> {
     fn clone(&self) -> Self {
-        Self {
-            phantom: PhantomData,
-        }
+        *self
     }
 }
 
@@ -82,33 +697,118 @@ impl<T: Ord> Collate for Collator<T> {
     }
 }
 
+// a generic impl of `CollateRef<Q>` for every `Collator<T>` where `T: Borrow<Q>` would overlap
+// the impl of `CollateRef<T>`, so the borrowed forms of standard library types are listed here
+macro_rules! collate_borrowed {
+    ($($owned:ty => $borrowed:ty),*) => {
+        $(
+            impl CollateRef<$borrowed> for Collator<$owned> {
+                fn cmp_ref(&self, left: &$borrowed, right: &$borrowed) -> Ordering {
+                    left.cmp(right)
+                }
+            }
+        )*
+    };
+}
+
+collate_borrowed!(
+    String => str,
+    std::path::PathBuf => std::path::Path,
+    std::ffi::OsString => std::ffi::OsStr,
+    std::ffi::CString => std::ffi::CStr
+);
+
+impl<T: Ord> CollateRef<[T]> for Collator<Vec<T>> {
+    fn cmp_ref(&self, left: &[T], right: &[T]) -> Ordering {
+        left.cmp(right)
+    }
+}
+
 /// An [`Overlap`] is the result of a comparison between two ranges,
 /// the equivalent of [`Ordering`] for hierarchical data.
+///
+/// The `u8` representation of each variant is stable, so it can be persisted.
 #[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd)]
+#[repr(u8)]
 pub enum Overlap {
     /// A lack of overlap where the compared range is entirely less than another
-    Less,
+    Less = 0,
 
     /// A lack of overlap where the compared range is entirely greater than another
-    Greater,
+    Greater = 1,
 
     /// An overlap where the compared range is identical to another
-    Equal,
+    Equal = 2,
 
     /// An overlap where the compared range is narrower than another
-    Narrow,
+    Narrow = 3,
 
     /// An overlap where the compared range is wider than another on both sides
-    Wide,
+    Wide = 4,
 
     /// An overlap where the compared range is wider than another with a lesser start and end point
-    WideLess,
+    WideLess = 5,
 
     /// An overlap where the compared range is wider than another with a greater start and end point
-    WideGreater,
+    WideGreater = 6,
 }
 
 impl Overlap {
+    /// A mask of every [`Overlap`] which indicates that the compared ranges overlap.
+    pub const ANY_OVERLAP: u8 = Self::Equal.mask()
+        | Self::Narrow.mask()
+        | Self::Wide.mask()
+        | Self::WideLess.mask()
+        | Self::WideGreater.mask();
+
+    /// A mask of every [`Overlap`] where the compared range is wider than another.
+    pub const ANY_WIDE: u8 = Self::Wide.mask() | Self::WideLess.mask() | Self::WideGreater.mask();
+
+    /// Return the stable `u8` representation of this [`Overlap`].
+    pub const fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// Construct an [`Overlap`] from its `u8` representation, if valid.
+    ///
+    /// Examples:
+    /// ```
+    /// use collate::Overlap;
+    /// assert_eq!(Overlap::try_from_u8(Overlap::WideLess.to_u8()), Some(Overlap::WideLess));
+    /// assert_eq!(Overlap::try_from_u8(7), None);
+    /// ```
+    pub const fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Less),
+            1 => Some(Self::Greater),
+            2 => Some(Self::Equal),
+            3 => Some(Self::Narrow),
+            4 => Some(Self::Wide),
+            5 => Some(Self::WideLess),
+            6 => Some(Self::WideGreater),
+            _ => None,
+        }
+    }
+
+    /// Return a single-bit mask of this [`Overlap`], for use with a set of [`Overlap`]s
+    /// such as [`Overlap::ANY_OVERLAP`] or [`Overlap::ANY_WIDE`].
+    ///
+    /// Examples:
+    /// ```
+    /// use collate::Overlap;
+    /// assert!(Overlap::Narrow.is_in(Overlap::ANY_OVERLAP));
+    /// assert!(!Overlap::Narrow.is_in(Overlap::ANY_WIDE));
+    /// assert!(!Overlap::Less.is_in(Overlap::ANY_OVERLAP));
+    /// ```
+    pub const fn mask(self) -> u8 {
+        1 << self as u8
+    }
+
+    /// Return `true` if this [`Overlap`] is in the given `mask`.
+    pub const fn is_in(self, mask: u8) -> bool {
+        self.mask() & mask != 0
+    }
+
     /// Return the narrowest [`Overlap`] which contains both `self` and `other`.
     /// Examples:
     /// ```
@@ -144,6 +844,94 @@ impl Overlap {
             },
         }
     }
+
+    /// Given that a range `a` overlaps a range `r` as `self` and a range `b` overlaps `r` as
+    /// `other`, return how the union of `a` and `b` (including any gap between them) overlaps `r`.
+    ///
+    /// Unlike [`Overlap::then`], the union of an [`Overlap::Narrow`] range and an
+    /// [`Overlap::Equal`] range is [`Overlap::Equal`]. The union of two [`Overlap::Narrow`] ranges
+    /// may be equal to `r`, but is reported as [`Overlap::Narrow`].
+    ///
+    /// |union      |Less    |Greater    |Equal      |Narrow     |Wide|WideLess|WideGreater|
+    /// |-----------|--------|-----------|-----------|-----------|----|--------|-----------|
+    /// |Less       |Less    |Wide       |WideLess   |WideLess   |Wide|WideLess|Wide       |
+    /// |Greater    |Wide    |Greater    |WideGreater|WideGreater|Wide|Wide    |WideGreater|
+    /// |Equal      |WideLess|WideGreater|Equal      |Equal      |Wide|WideLess|WideGreater|
+    /// |Narrow     |WideLess|WideGreater|Equal      |Narrow     |Wide|WideLess|WideGreater|
+    /// |Wide       |Wide    |Wide       |Wide       |Wide       |Wide|Wide    |Wide       |
+    /// |WideLess   |WideLess|Wide       |WideLess   |WideLess   |Wide|WideLess|Wide       |
+    /// |WideGreater|Wide    |WideGreater|WideGreater|WideGreater|Wide|Wide    |WideGreater|
+    ///
+    /// Examples:
+    /// ```
+    /// use collate::Overlap;
+    /// assert_eq!(Overlap::Narrow.union(Overlap::Equal), Overlap::Equal);
+    /// assert_eq!(Overlap::Less.union(Overlap::Narrow), Overlap::WideLess);
+    /// assert_eq!(Overlap::Less.union(Overlap::Greater), Overlap::Wide);
+    /// ```
+    pub fn union(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Wide, _) | (_, Self::Wide) => Self::Wide,
+            (Self::Less, Self::Less) => Self::Less,
+            (Self::Greater, Self::Greater) => Self::Greater,
+            (Self::Less | Self::WideLess, Self::Greater | Self::WideGreater) => Self::Wide,
+            (Self::Greater | Self::WideGreater, Self::Less | Self::WideLess) => Self::Wide,
+            (Self::Less | Self::WideLess, _) | (_, Self::Less | Self::WideLess) => Self::WideLess,
+            (Self::Greater | Self::WideGreater, _) | (_, Self::Greater | Self::WideGreater) => {
+                Self::WideGreater
+            }
+            (Self::Equal, _) | (_, Self::Equal) => Self::Equal,
+            (Self::Narrow, Self::Narrow) => Self::Narrow,
+        }
+    }
+
+    /// Given that a range `a` overlaps a range `r` as `self` and a range `b` overlaps `r` as
+    /// `other`, return how the intersection of `a` and `b` overlaps `r`,
+    /// or `None` if the intersection of `a` and `b` is necessarily empty.
+    ///
+    /// The intersection may still be empty if this returns `Some`, e.g. two ranges which are both
+    /// [`Overlap::Less`] than `r` do not necessarily overlap each other. In that case the result
+    /// describes the intersection if it's not empty. An intersection which may be equal to `r`
+    /// or narrower than `r` is reported as [`Overlap::Narrow`].
+    ///
+    /// |intersect  |Less|Greater|Equal |Narrow|Wide       |WideLess|WideGreater|
+    /// |-----------|----|-------|------|------|-----------|--------|-----------|
+    /// |Less       |Less|None   |None  |None  |Less       |Less    |None       |
+    /// |Greater    |None|Greater|None  |None  |Greater    |None    |Greater    |
+    /// |Equal      |None|None   |Equal |Narrow|Equal      |Narrow  |Narrow     |
+    /// |Narrow     |None|None   |Narrow|Narrow|Narrow     |Narrow  |Narrow     |
+    /// |Wide       |Less|Greater|Equal |Narrow|Wide       |WideLess|WideGreater|
+    /// |WideLess   |Less|None   |Narrow|Narrow|WideLess   |WideLess|Narrow     |
+    /// |WideGreater|None|Greater|Narrow|Narrow|WideGreater|Narrow  |WideGreater|
+    ///
+    /// Examples:
+    /// ```
+    /// use collate::Overlap;
+    /// assert_eq!(Overlap::Wide.intersect(Overlap::Narrow), Some(Overlap::Narrow));
+    /// assert_eq!(Overlap::WideLess.intersect(Overlap::WideGreater), Some(Overlap::Narrow));
+    /// assert_eq!(Overlap::Less.intersect(Overlap::Equal), None);
+    /// ```
+    pub fn intersect(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (Self::Wide, other) | (other, Self::Wide) => Some(other),
+            (Self::Less, Self::Less | Self::WideLess) | (Self::WideLess, Self::Less) => {
+                Some(Self::Less)
+            }
+            (Self::Greater, Self::Greater | Self::WideGreater)
+            | (Self::WideGreater, Self::Greater) => Some(Self::Greater),
+            (Self::Less | Self::Greater, _) | (_, Self::Less | Self::Greater) => None,
+            (Self::WideLess, Self::WideLess) => Some(Self::WideLess),
+            (Self::WideGreater, Self::WideGreater) => Some(Self::WideGreater),
+            (Self::Equal, Self::Equal) => Some(Self::Equal),
+            _ => Some(Self::Narrow),
+        }
+    }
+}
+
+impl From<Overlap> for u8 {
+    fn from(overlap: Overlap) -> Self {
+        overlap.to_u8()
+    }
 }
 
 impl From<Ordering> for Overlap {
@@ -161,20 +949,23 @@ pub trait OverlapsRange<T, C: Collate> {
     /// Check whether `other` lies entirely within `self` according to the given `collator`.
     #[inline]
     fn contains(&self, other: &T, collator: &C) -> bool {
-        match self.overlaps(other, collator) {
-            Overlap::Wide | Overlap::Equal => true,
-            _ => false,
-        }
+        matches!(
+            self.overlaps(other, collator),
+            Overlap::Wide | Overlap::Equal
+        )
     }
 
     /// Check whether `other` lies partially within `self` according to the given `collator`.
     #[inline]
     fn contains_partial(&self, other: &T, collator: &C) -> bool {
-        match self.overlaps(other, collator) {
-            Overlap::Narrow | Overlap::Equal => true,
-            Overlap::WideLess | Overlap::Wide | Overlap::WideGreater => true,
-            _ => false,
-        }
+        matches!(
+            self.overlaps(other, collator),
+            Overlap::Narrow
+                | Overlap::Equal
+                | Overlap::WideLess
+                | Overlap::Wide
+                | Overlap::WideGreater
+        )
     }
 
     /// Check whether `self` overlaps `other` according to the given `collator`.
@@ -191,6 +982,8 @@ pub trait OverlapsRange<T, C: Collate> {
     /// assert_eq!((1..).overlaps(&(3..5), &collator), Overlap::Wide);
     /// assert_eq!((1..4).overlaps(&(3..), &collator), Overlap::WideLess);
     /// assert_eq!((3..5).overlaps(&(..4), &collator), Overlap::WideGreater);
+    /// assert_eq!((1..=5).overlaps(&(2..3), &collator), Overlap::Wide);
+    /// assert_eq!((..=4).overlaps(&(4..5), &collator), Overlap::WideLess);
     /// ```
     fn overlaps(&self, other: &T, collator: &C) -> Overlap;
 }
@@ -292,6 +1085,14 @@ overlaps_range!(RangeFrom<C::Value>, RangeInclusive<C::Value>);
 overlaps_range!(RangeFrom<C::Value>, RangeTo<C::Value>);
 overlaps_range!(RangeFrom<C::Value>, RangeToInclusive<C::Value>);
 
+overlaps_range!(RangeInclusive<C::Value>, (Bound<C::Value>, Bound<C::Value>));
+overlaps_range!(RangeInclusive<C::Value>, Range<C::Value>);
+overlaps_range!(RangeInclusive<C::Value>, RangeFull);
+overlaps_range!(RangeInclusive<C::Value>, RangeFrom<C::Value>);
+overlaps_range!(RangeInclusive<C::Value>, RangeInclusive<C::Value>);
+overlaps_range!(RangeInclusive<C::Value>, RangeTo<C::Value>);
+overlaps_range!(RangeInclusive<C::Value>, RangeToInclusive<C::Value>);
+
 overlaps_range!(RangeTo<C::Value>, (Bound<C::Value>, Bound<C::Value>));
 overlaps_range!(RangeTo<C::Value>, Range<C::Value>);
 overlaps_range!(RangeTo<C::Value>, RangeFull);
@@ -300,6 +1101,17 @@ overlaps_range!(RangeTo<C::Value>, RangeInclusive<C::Value>);
 overlaps_range!(RangeTo<C::Value>, RangeTo<C::Value>);
 overlaps_range!(RangeTo<C::Value>, RangeToInclusive<C::Value>);
 
+overlaps_range!(
+    RangeToInclusive<C::Value>,
+    (Bound<C::Value>, Bound<C::Value>)
+);
+overlaps_range!(RangeToInclusive<C::Value>, Range<C::Value>);
+overlaps_range!(RangeToInclusive<C::Value>, RangeFull);
+overlaps_range!(RangeToInclusive<C::Value>, RangeFrom<C::Value>);
+overlaps_range!(RangeToInclusive<C::Value>, RangeInclusive<C::Value>);
+overlaps_range!(RangeToInclusive<C::Value>, RangeTo<C::Value>);
+overlaps_range!(RangeToInclusive<C::Value>, RangeToInclusive<C::Value>);
+
 overlaps_range!(
     (Bound<C::Value>, Bound<C::Value>),
     (Bound<C::Value>, Bound<C::Value>)
@@ -318,10 +1130,10 @@ overlaps_range!(
 pub trait OverlapsValue<T, C: Collate> {
     /// Return `true` if this range contains `value` according to `collator`.
     fn contains_value(&self, value: &T, collator: &C) -> bool {
-        match self.overlaps_value(value, collator) {
-            Overlap::Less | Overlap::Greater => false,
-            _ => true,
-        }
+        !matches!(
+            self.overlaps_value(value, collator),
+            Overlap::Less | Overlap::Greater
+        )
     }
 
     /// Return `true` if this range overlaps `value` according to `collator`.
@@ -350,7 +1162,7 @@ overlaps_value!(RangeTo<T>);
 overlaps_value!(RangeToInclusive<T>);
 
 #[inline]
-fn cmp_bound<'a, T, C>(
+pub(crate) fn cmp_bound<'a, T, C>(
     collator: &'a C,
     left: Bound<&'a T>,
     right: Bound<&'a T>,
@@ -377,7 +1189,7 @@ where
     }
 }
 
-fn overlaps<T, C, L, R>(collator: &C, left: &L, right: &R) -> Overlap
+pub(crate) fn overlaps<T, C, L, R>(collator: &C, left: &L, right: &R) -> Overlap
 where
     C: CollateRef<T>,
     L: RangeBounds<T>,
@@ -439,7 +1251,7 @@ where
 }
 
 #[inline]
-fn overlaps_value<T, C, R>(range: &R, value: &T, collator: &C) -> Overlap
+pub(crate) fn overlaps_value<T, C, R>(range: &R, value: &T, collator: &C) -> Overlap
 where
     C: CollateRef<T>,
     R: RangeBounds<T>,
@@ -479,3 +1291,72 @@ where
         (Ordering::Less, Ordering::Equal) => Overlap::WideLess,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OVERLAPS: [Overlap; 7] = [
+        Overlap::Less,
+        Overlap::Greater,
+        Overlap::Equal,
+        Overlap::Narrow,
+        Overlap::Wide,
+        Overlap::WideLess,
+        Overlap::WideGreater,
+    ];
+
+    // return `true` if the `expected` overlap is described by the `actual` result
+    fn describes(actual: Overlap, expected: Overlap) -> bool {
+        actual == expected || (actual == Overlap::Narrow && expected == Overlap::Equal)
+    }
+
+    #[test]
+    fn test_overlap_union_and_intersect() {
+        let collator = Collator::<u8>::default();
+        let reference = 3..7;
+
+        let ranges = (0..10u8)
+            .flat_map(|start| (start + 1..=10).map(move |end| start..end))
+            .collect::<Vec<_>>();
+
+        for a in &ranges {
+            let a_overlap = a.overlaps(&reference, &collator);
+
+            for b in &ranges {
+                let b_overlap = b.overlaps(&reference, &collator);
+
+                let union = a.start.min(b.start)..a.end.max(b.end);
+                let expected = union.overlaps(&reference, &collator);
+                assert!(describes(a_overlap.union(b_overlap), expected));
+
+                let intersection = a.start.max(b.start)..a.end.min(b.end);
+                match a_overlap.intersect(b_overlap) {
+                    None => assert!(intersection.is_empty(), "{a:?} and {b:?} intersect"),
+                    Some(actual) if !intersection.is_empty() => {
+                        let expected = intersection.overlaps(&reference, &collator);
+                        assert!(describes(actual, expected));
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        // union and intersection are commutative
+        for a in OVERLAPS {
+            for b in OVERLAPS {
+                assert_eq!(a.union(b), b.union(a));
+                assert_eq!(a.intersect(b), b.intersect(a));
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_collator_serde() {
+        let collator = Collator::<u32>::default();
+        let serialized = serde_json::to_string(&collator).expect("JSON");
+        let deserialized: Collator<u32> = serde_json::from_str(&serialized).expect("collator");
+        assert!(collator == deserialized);
+    }
+}
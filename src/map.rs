@@ -0,0 +1,99 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::Collate;
+
+/// A collator for values of type `U` which collates a field borrowed from each value using
+/// another collator. Construct a [`MapRef`] collator using [`Collate::map_ref`].
+///
+/// Two [`MapRef`] collators are equal if their inner collators are equal;
+/// the projections themselves are not compared.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, Collator};
+///
+/// struct UserId(String);
+///
+/// let collator = Collator::<String>::default().map_ref(|id: &UserId| &id.0);
+///
+/// let mut ids = [UserId("bob".into()), UserId("alice".into())];
+/// ids.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(ids[0].0, "alice");
+/// ```
+pub struct MapRef<C, F, U> {
+    collator: C,
+    project: F,
+    phantom: PhantomData<fn(&U)>,
+}
+
+impl<C, F, U> MapRef<C, F, U> {
+    /// Construct a new [`MapRef`] collator.
+    pub fn new(collator: C, project: F) -> Self {
+        Self {
+            collator,
+            project,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Borrow the collator used to collate the projected field.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+}
+
+impl<C: Clone, F: Clone, U> Clone for MapRef<C, F, U> {
+    fn clone(&self) -> Self {
+        Self::new(self.collator.clone(), self.project.clone())
+    }
+}
+
+impl<C: Copy, F: Copy, U> Copy for MapRef<C, F, U> {}
+
+impl<C: fmt::Debug, F, U> fmt::Debug for MapRef<C, F, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("MapRef").field(&self.collator).finish()
+    }
+}
+
+impl<C: PartialEq, F, U> PartialEq for MapRef<C, F, U> {
+    fn eq(&self, other: &Self) -> bool {
+        self.collator == other.collator
+    }
+}
+
+impl<C: Eq, F, U> Eq for MapRef<C, F, U> {}
+
+impl<C, F, U> Collate for MapRef<C, F, U>
+where
+    C: Collate,
+    F: Fn(&U) -> &C::Value,
+{
+    type Value = U;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.collator
+            .cmp((self.project)(left), (self.project)(right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_map_ref() {
+        let descending = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let collator = descending.map_ref(|pair: &(u32, &str)| &pair.0);
+
+        let mut pairs = [(1, "a"), (3, "b"), (2, "c"), (3, "d")];
+        pairs.sort_by(|l, r| collator.cmp(l, r));
+        assert_eq!(pairs, [(3, "b"), (3, "d"), (2, "c"), (1, "a")]);
+
+        let collator = collator.then(Collator::default().map_ref(|pair: &(u32, &str)| &pair.1));
+        assert_eq!(collator.cmp(&(3, "b"), &(3, "d")), Ordering::Less);
+    }
+}
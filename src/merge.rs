@@ -0,0 +1,197 @@
+use std::cmp::Ordering;
+use std::ptr;
+
+use crate::CollateRef;
+
+/// Merge two collated `Vec`s into one using the given `collator`, like the `merge` stream
+/// combinator (with the "stream" feature flag) but without any async machinery.
+///
+/// Both inputs **must** be collated. As with `merge`, when two values collate equal
+/// the value from `left` is kept and the value from `right` is dropped.
+///
+/// Example:
+/// ```
+/// use collate::{merge_vecs, Collator};
+///
+/// let merged = merge_vecs(Collator::default(), vec![1, 3, 5], vec![2, 3, 4]);
+/// assert_eq!(merged, [1, 2, 3, 4, 5]);
+/// ```
+pub fn merge_vecs<C, T>(collator: C, left: Vec<T>, right: Vec<T>) -> Vec<T>
+where
+    C: CollateRef<T>,
+{
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+
+    loop {
+        let order = match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) => collator.cmp_ref(l, r),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => break,
+        };
+
+        match order {
+            Ordering::Less => merged.extend(left.next()),
+            Ordering::Greater => merged.extend(right.next()),
+            Ordering::Equal => {
+                right.next();
+                merged.extend(left.next());
+            }
+        }
+    }
+
+    merged
+}
+
+/// Merge the collated `Vec` `other` into the collated `Vec` `target` in place, using the given
+/// `collator`. See [`merge_vecs`] for details.
+///
+/// This reserves space for `other` in `target` and merges back-to-front, so it doesn't allocate
+/// if `target` already has enough spare capacity.
+/// If the collator panics, `target` keeps all the values of both inputs but may not be collated.
+///
+/// Example:
+/// ```
+/// use collate::{merge_into, Collator};
+///
+/// let mut values = vec!["a", "c"];
+/// merge_into(Collator::default(), &mut values, vec!["b", "c", "d"]);
+/// assert_eq!(values, ["a", "b", "c", "d"]);
+/// ```
+pub fn merge_into<C, T>(collator: C, target: &mut Vec<T>, mut other: Vec<T>)
+where
+    C: CollateRef<T>,
+{
+    // drop each value in `other` which collates equal to a value in `target`, pairing equal
+    // values in order as `merge_vecs` does, so that the merge below never needs to drop anything
+    let mut i = 0;
+    other.retain(|value| {
+        while let Some(existing) = target.get(i) {
+            match collator.cmp_ref(existing, value) {
+                Ordering::Less => i += 1,
+                Ordering::Equal => {
+                    i += 1;
+                    return false;
+                }
+                Ordering::Greater => break,
+            }
+        }
+
+        true
+    });
+
+    if other.is_empty() {
+        return;
+    }
+
+    target.reserve(other.len());
+
+    let mut hole = MergeHole {
+        len: target.len() + other.len(),
+        left: target.len(),
+        right: other.len(),
+        src: other.as_ptr(),
+        target,
+    };
+
+    // SAFETY: the values in `other` are now owned by `hole`, which moves them into `target`
+    unsafe { other.set_len(0) };
+
+    while hole.left > 0 && hole.right > 0 {
+        let dest = hole.left + hole.right - 1;
+        let base = hole.target.as_mut_ptr();
+
+        // SAFETY: `base[..hole.left]` and `src[..hole.right]` are the values not yet merged,
+        // and `dest` is the last vacant slot, which lies after `base[..hole.left]`
+        unsafe {
+            let left = base.add(hole.left - 1);
+            let right = hole.src.add(hole.right - 1);
+
+            // values in `target` go before equal values in `other`
+            if collator.cmp_ref(&*left, &*right) == Ordering::Greater {
+                ptr::copy_nonoverlapping(left, base.add(dest), 1);
+                hole.left -= 1;
+            } else {
+                ptr::copy_nonoverlapping(right, base.add(dest), 1);
+                hole.right -= 1;
+            }
+        }
+    }
+
+    // dropping `hole` moves any values remaining in `other` into place
+}
+
+// The state of an in-place merge into `target`, whose values are laid out as
+// `[unmerged values of target, vacant slots, merged values]`, where the number of vacant slots
+// is the number of unmerged values left in `src`.
+// On drop (including if the collator panics) the unmerged values of `src` fill the vacant slots,
+// so that every value ends up owned by `target` exactly once.
+struct MergeHole<'a, T> {
+    target: &'a mut Vec<T>,
+    src: *const T,
+    len: usize,
+    left: usize,
+    right: usize,
+}
+
+impl<'a, T> Drop for MergeHole<'a, T> {
+    fn drop(&mut self) {
+        // SAFETY: `target` has capacity for `len` values, and after this copy all of them
+        // are initialized
+        unsafe {
+            let dest = self.target.as_mut_ptr().add(self.left);
+            ptr::copy_nonoverlapping(self.src, dest, self.right);
+            self.target.set_len(self.len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collate, Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_merge_vecs() {
+        let collator = DirectedCollator::new(Collator::<i32>::default(), Direction::Descending);
+
+        let merged = merge_vecs(collator, vec![9, 5, 5, 1], vec![8, 5, 0, -1]);
+        assert_eq!(merged, [9, 8, 5, 5, 1, 0, -1]);
+
+        let mut target = vec![];
+        merge_into(collator, &mut target, vec![3, 2]);
+        merge_into(collator, &mut target, vec![]);
+        merge_into(collator, &mut target, vec![4, 2, 1]);
+        assert_eq!(target, [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_merge_into() {
+        let collator = Collator::<u32>::default().map_ref(|(key, _): &(u32, &str)| key);
+
+        let mut target = Vec::with_capacity(8);
+        target.extend([(1, "a"), (3, "a"), (3, "b"), (5, "a")]);
+        let other = vec![(0, "c"), (3, "c"), (3, "d"), (3, "e"), (4, "c"), (6, "c")];
+
+        let expected = merge_vecs(collator, target.clone(), other.clone());
+        merge_into(collator, &mut target, other);
+
+        assert_eq!(target, expected);
+        assert_eq!(
+            target,
+            [
+                (0, "c"),
+                (1, "a"),
+                (3, "a"),
+                (3, "b"),
+                (3, "e"),
+                (4, "c"),
+                (5, "a"),
+                (6, "c")
+            ]
+        );
+        assert_eq!(target.capacity(), 8);
+    }
+}
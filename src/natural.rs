@@ -0,0 +1,126 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// A collator for strings which compares runs of ASCII digits by their numeric value,
+/// so that e.g. `"file2"` collates before `"file10"`.
+///
+/// Other characters are compared by code point. Numbers of any length are supported,
+/// since digit runs are compared without parsing them. Strings which differ only in the
+/// leading zeros of a number (like `"a01"` and `"a1"`) are collated by code point.
+///
+/// Example:
+/// ```
+/// use collate::{CollateRef, NaturalCollator};
+///
+/// let mut files = ["file10.txt", "file2.txt", "file1.txt"];
+/// files.sort_by(|l, r| NaturalCollator.cmp_ref(*l, *r));
+/// assert_eq!(files, ["file1.txt", "file2.txt", "file10.txt"]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NaturalCollator;
+
+impl Collate for NaturalCollator {
+    type Value = String;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_str(), right.as_str())
+    }
+}
+
+impl CollateRef<str> for NaturalCollator {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        cmp_natural(left, right).then_with(|| left.cmp(right))
+    }
+}
+
+fn cmp_natural(mut left: &str, mut right: &str) -> Ordering {
+    loop {
+        let (l, r) = match (left.chars().next(), right.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) => (l, r),
+        };
+
+        let order = if l.is_ascii_digit() && r.is_ascii_digit() {
+            let (l_digits, l_rest) = split_digits(left);
+            let (r_digits, r_rest) = split_digits(right);
+
+            left = l_rest;
+            right = r_rest;
+
+            cmp_digits(l_digits, r_digits)
+        } else {
+            left = &left[l.len_utf8()..];
+            right = &right[r.len_utf8()..];
+
+            l.cmp(&r)
+        };
+
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+// compare two runs of ASCII digits by numeric value
+fn cmp_digits(left: &str, right: &str) -> Ordering {
+    let left = left.trim_start_matches('0');
+    let right = right.trim_start_matches('0');
+
+    // with no leading zeros, a longer number is always greater
+    left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+}
+
+fn split_digits(value: &str) -> (&str, &str) {
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+
+    value.split_at(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_order() {
+        let mut values = vec![
+            "x10",
+            "x9",
+            "x",
+            "x09",
+            "x9y",
+            "x9a",
+            "y1",
+            "x100000000000000000000000",
+            "x0",
+        ];
+
+        values.sort_by(|l, r| NaturalCollator.cmp_ref(*l, *r));
+
+        assert_eq!(
+            values,
+            vec![
+                "x",
+                "x0",
+                "x09",
+                "x9",
+                "x9a",
+                "x9y",
+                "x10",
+                "x100000000000000000000000",
+                "y1"
+            ]
+        );
+
+        assert_eq!(NaturalCollator.cmp_ref("a1b2", "a1b2"), Ordering::Equal);
+        assert_eq!(NaturalCollator.cmp_ref("a01", "a1"), Ordering::Less);
+    }
+}
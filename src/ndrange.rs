@@ -0,0 +1,166 @@
+use std::ops::Bound;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{overlaps, overlaps_value, Collate, CollateRef, Overlap, OverlapsRange, OverlapsValue};
+
+/// A multi-dimensional range, with a range of values of type `V` along each axis,
+/// e.g. to select a slice of a tensor or a region of a spatial index.
+///
+/// If one [`NDRange`] has fewer axes than another, its missing trailing axes are unbounded.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, NDRange, Overlap, OverlapsRange, OverlapsValue};
+///
+/// let collator = Collator::<u64>::default();
+/// let region = NDRange::new(vec![
+///     (Bound::Included(0), Bound::Excluded(10)),
+///     (Bound::Included(0), Bound::Excluded(10)),
+/// ]);
+///
+/// let tile = NDRange::new(vec![
+///     (Bound::Included(2), Bound::Excluded(4)),
+///     (Bound::Included(3), Bound::Excluded(5)),
+/// ]);
+/// assert_eq!(region.overlaps(&tile, &collator), Overlap::Wide);
+/// assert_eq!(tile.overlaps(&region, &collator), Overlap::Narrow);
+/// assert!(region.contains(&tile, &collator));
+///
+/// let tile = NDRange::new(vec![
+///     (Bound::Included(2), Bound::Excluded(4)),
+///     (Bound::Included(10), Bound::Excluded(20)),
+/// ]);
+/// assert_eq!(region.overlaps(&tile, &collator), Overlap::Less);
+///
+/// assert!(region.contains_value(&vec![3, 9], &collator));
+/// assert!(!region.contains_value(&vec![3, 10], &collator));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NDRange<V> {
+    axes: Vec<(Bound<V>, Bound<V>)>,
+}
+
+impl<V> NDRange<V> {
+    /// Construct a new [`NDRange`] with the given range along each axis.
+    pub fn new(axes: Vec<(Bound<V>, Bound<V>)>) -> Self {
+        Self { axes }
+    }
+
+    /// Borrow the range along each axis of this [`NDRange`].
+    pub fn axes(&self) -> &[(Bound<V>, Bound<V>)] {
+        &self.axes
+    }
+
+    /// Return the number of axes of this [`NDRange`] which are explicitly bounded.
+    pub fn ndim(&self) -> usize {
+        self.axes.len()
+    }
+
+    /// Destructure this [`NDRange`] into its range along each axis.
+    pub fn into_axes(self) -> Vec<(Bound<V>, Bound<V>)> {
+        self.axes
+    }
+
+    // borrow the range along the given axis, which is unbounded if not specified
+    fn axis(&self, axis: usize) -> (Bound<&V>, Bound<&V>) {
+        match self.axes.get(axis) {
+            Some((start, end)) => (start.as_ref(), end.as_ref()),
+            None => (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+}
+
+impl<V> From<Vec<(Bound<V>, Bound<V>)>> for NDRange<V> {
+    fn from(axes: Vec<(Bound<V>, Bound<V>)>) -> Self {
+        Self::new(axes)
+    }
+}
+
+impl<C: Collate> OverlapsRange<NDRange<C::Value>, C> for NDRange<C::Value> {
+    /// Check whether every axis of `other` lies entirely within the same axis of `self`.
+    fn contains(&self, other: &NDRange<C::Value>, collator: &C) -> bool {
+        axis_overlaps(self, other, collator)
+            .all(|overlap| matches!(overlap, Overlap::Wide | Overlap::Equal))
+    }
+
+    /// Compare `self` to `other` along each axis, combining the [`Overlap`] of each axis
+    /// using [`Overlap::then`], except that if the ranges are disjoint along any axis then
+    /// the result is the [`Overlap::Less`] or [`Overlap::Greater`] of the first such axis.
+    ///
+    /// Note that a range which is narrower than `other` along one axis but wider along another
+    /// is reported as wider, so use [`OverlapsRange::contains`] to check containment.
+    fn overlaps(&self, other: &NDRange<C::Value>, collator: &C) -> Overlap {
+        combine(axis_overlaps(self, other, collator))
+    }
+}
+
+impl<V, C: CollateRef<V>> OverlapsValue<Vec<V>, C> for NDRange<V> {
+    /// Compare each coordinate of the given `value` to the same axis of this range,
+    /// combining the [`Overlap`] of each axis like [`OverlapsRange::overlaps`].
+    /// An axis with no corresponding coordinate is ignored.
+    fn overlaps_value(&self, value: &Vec<V>, collator: &C) -> Overlap {
+        combine(
+            value
+                .iter()
+                .enumerate()
+                .map(|(axis, coord)| overlaps_value(&self.axis(axis), coord, collator)),
+        )
+    }
+}
+
+fn axis_overlaps<'a, C: Collate>(
+    left: &'a NDRange<C::Value>,
+    right: &'a NDRange<C::Value>,
+    collator: &'a C,
+) -> impl Iterator<Item = Overlap> + 'a {
+    let ndim = left.axes.len().max(right.axes.len());
+    (0..ndim).map(move |axis| overlaps(collator, &left.axis(axis), &right.axis(axis)))
+}
+
+fn combine<I: IntoIterator<Item = Overlap>>(overlaps: I) -> Overlap {
+    let mut combined = Overlap::Equal;
+
+    for overlap in overlaps {
+        match overlap {
+            Overlap::Less | Overlap::Greater => return overlap,
+            overlap => combined = combined.then(overlap),
+        }
+    }
+
+    combined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_ndrange() {
+        let collator = Collator::<i32>::default();
+        let wide = NDRange::new(vec![(Bound::Unbounded, Bound::Unbounded)]);
+        let square = NDRange::new(vec![
+            (Bound::Included(0), Bound::Excluded(4)),
+            (Bound::Included(0), Bound::Excluded(4)),
+        ]);
+
+        // a missing axis is unbounded
+        assert!(wide.contains(&square, &collator));
+        assert!(!square.contains(&wide, &collator));
+        assert_eq!(square.overlaps(&square, &collator), Overlap::Equal);
+
+        let shifted = NDRange::new(vec![
+            (Bound::Included(2), Bound::Excluded(6)),
+            (Bound::Included(-2), Bound::Excluded(2)),
+        ]);
+
+        assert!(square.contains_partial(&shifted, &collator));
+        assert!(!square.contains(&shifted, &collator));
+        assert!(square.contains_value(&vec![3, 3], &collator));
+        assert!(!square.contains_value(&vec![3, -1], &collator));
+    }
+}
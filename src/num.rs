@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+
+use num_bigint::{BigInt, BigUint};
+use num_rational::BigRational;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateEquivalent};
+
+/// A collator for arbitrary-precision integers, which can also compare a stored [`BigInt`]
+/// to an `i64`, `u64`, or [`BigUint`] query using [`CollateEquivalent`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{BigIntCollator, CollateEquivalent};
+/// use num_bigint::BigInt;
+///
+/// let key = BigInt::from(u64::MAX) * 2;
+/// assert_eq!(BigIntCollator.cmp_equivalent(&key, &i64::MAX), Ordering::Greater);
+/// assert_eq!(BigIntCollator.cmp_equivalent(&-key, &i64::MIN), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BigIntCollator;
+
+impl Collate for BigIntCollator {
+    type Value = BigInt;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        left.cmp(right)
+    }
+}
+
+impl CollateEquivalent<BigInt, i64> for BigIntCollator {
+    fn cmp_equivalent(&self, key: &BigInt, query: &i64) -> Ordering {
+        key.cmp(&BigInt::from(*query))
+    }
+}
+
+impl CollateEquivalent<BigInt, u64> for BigIntCollator {
+    fn cmp_equivalent(&self, key: &BigInt, query: &u64) -> Ordering {
+        key.cmp(&BigInt::from(*query))
+    }
+}
+
+impl CollateEquivalent<BigInt, BigUint> for BigIntCollator {
+    fn cmp_equivalent(&self, key: &BigInt, query: &BigUint) -> Ordering {
+        key.cmp(&BigInt::from(query.clone()))
+    }
+}
+
+/// A collator for arbitrary-precision unsigned integers, which can also compare a stored
+/// [`BigUint`] to a `u64` query using [`CollateEquivalent`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BigUintCollator;
+
+impl Collate for BigUintCollator {
+    type Value = BigUint;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        left.cmp(right)
+    }
+}
+
+impl CollateEquivalent<BigUint, u64> for BigUintCollator {
+    fn cmp_equivalent(&self, key: &BigUint, query: &u64) -> Ordering {
+        key.cmp(&BigUint::from(*query))
+    }
+}
+
+/// A collator for arbitrary-precision rational numbers, which can also compare a stored
+/// [`BigRational`] to an `i64` or [`BigInt`] query using [`CollateEquivalent`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CollateEquivalent, RatioCollator};
+/// use num_rational::BigRational;
+///
+/// let key = BigRational::new(7.into(), 2.into());
+/// assert_eq!(RatioCollator.cmp_equivalent(&key, &3i64), Ordering::Greater);
+/// assert_eq!(RatioCollator.cmp_equivalent(&key, &4i64), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RatioCollator;
+
+impl Collate for RatioCollator {
+    type Value = BigRational;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        left.cmp(right)
+    }
+}
+
+impl CollateEquivalent<BigRational, i64> for RatioCollator {
+    fn cmp_equivalent(&self, key: &BigRational, query: &i64) -> Ordering {
+        self.cmp_equivalent(key, &BigInt::from(*query))
+    }
+}
+
+impl CollateEquivalent<BigRational, BigInt> for RatioCollator {
+    fn cmp_equivalent(&self, key: &BigRational, query: &BigInt) -> Ordering {
+        // compare numer / denom to query, where the denominator is always positive
+        key.numer().cmp(&(query * key.denom()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MergedView;
+
+    #[test]
+    fn test_bigint_lookup() {
+        let base = [-1, 3, 5]
+            .into_iter()
+            .map(BigInt::from)
+            .chain([BigInt::from(u64::MAX) * 3])
+            .collect::<Vec<BigInt>>();
+
+        let view = MergedView::new(BigIntCollator, &base, &[]);
+        assert_eq!(view.get(&3i64), Some(&BigInt::from(3)));
+        assert_eq!(view.get(&4u64), None);
+        assert_eq!(view.range(0i64..).count(), 3);
+
+        let third = BigRational::new(BigInt::from(-1), BigInt::from(3));
+        assert_eq!(RatioCollator.cmp_equivalent(&third, &0i64), Ordering::Less);
+        assert_eq!(
+            RatioCollator.cmp_equivalent(&third, &-1i64),
+            Ordering::Greater
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, NullOrder};
+
+/// A collator for optional values, which places `None` before or after all `Some` values
+/// according to its [`NullOrder`], as in SQL `NULLS FIRST` or `NULLS LAST`.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, Collator, NullOrder, OptionCollator};
+///
+/// let collator = OptionCollator::new(Collator::<u32>::default(), NullOrder::Last);
+///
+/// let mut values = vec![Some(2), None, Some(1)];
+/// values.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(values, [Some(1), Some(2), None]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OptionCollator<C> {
+    collator: C,
+    nulls: NullOrder,
+}
+
+impl<C> OptionCollator<C> {
+    /// Construct a new [`OptionCollator`].
+    pub fn new(collator: C, nulls: NullOrder) -> Self {
+        Self { collator, nulls }
+    }
+
+    /// Construct a new [`OptionCollator`] which places `None` before all `Some` values.
+    pub fn nulls_first(collator: C) -> Self {
+        Self::new(collator, NullOrder::First)
+    }
+
+    /// Construct a new [`OptionCollator`] which places `None` after all `Some` values.
+    pub fn nulls_last(collator: C) -> Self {
+        Self::new(collator, NullOrder::Last)
+    }
+
+    /// Borrow the underlying collator.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+
+    /// The [`NullOrder`] of this collator.
+    pub fn nulls(&self) -> NullOrder {
+        self.nulls
+    }
+}
+
+impl<C: Collate> Collate for OptionCollator<C> {
+    type Value = Option<C::Value>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.nulls.cmp(left.as_ref(), right.as_ref(), |l, r| {
+            self.collator.cmp(l, r)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_option_collator() {
+        let collator = OptionCollator::nulls_first(Collator::<u32>::default());
+        assert_eq!(collator.cmp(&None, &Some(0)), Ordering::Less);
+        assert_eq!(collator.cmp(&None, &None), Ordering::Equal);
+        assert_eq!(collator.cmp(&Some(1), &Some(0)), Ordering::Greater);
+
+        // the null placement does not depend on the direction of the inner collator
+        let descending = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+        let collator = OptionCollator::nulls_last(descending);
+
+        let mut values = vec![None, Some(1), Some(3), None, Some(2)];
+        values.sort_by(|l, r| collator.cmp(l, r));
+        assert_eq!(values, [Some(3), Some(2), Some(1), None, None]);
+    }
+}
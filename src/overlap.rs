@@ -0,0 +1,104 @@
+use std::ops::Range;
+
+use crate::{Collate, Overlap, OverlapsRange};
+
+/// Return the indices of the `ranges` which overlap the given `query` range according to
+/// `collator`, using a binary search.
+///
+/// The `ranges` **must** be collated and disjoint, e.g. the key ranges of the children of a
+/// B-Tree node. If not, the indices returned are undefined.
+///
+/// Example:
+/// ```
+/// use collate::{overlapping_indices, Collator};
+///
+/// let ranges = [0..2, 2..4, 4..6, 6..8];
+/// let collator = Collator::default();
+/// assert_eq!(overlapping_indices(&ranges, &(3..5), &collator), 1..3);
+/// assert_eq!(overlapping_indices(&ranges, &(9..10), &collator), 4..4);
+/// ```
+pub fn overlapping_indices<R, Q, C>(ranges: &[R], query: &Q, collator: &C) -> Range<usize>
+where
+    R: OverlapsRange<Q, C>,
+    C: Collate,
+{
+    let start = ranges.partition_point(|range| range.overlaps(query, collator) == Overlap::Less);
+
+    let end = start
+        + ranges[start..]
+            .partition_point(|range| range.overlaps(query, collator) != Overlap::Greater);
+
+    start..end
+}
+
+/// Return the index of the first of the `ranges` which overlaps the given `query` range
+/// according to `collator`, if any. See [`overlapping_indices`] for details.
+pub fn first_overlapping<R, Q, C>(ranges: &[R], query: &Q, collator: &C) -> Option<usize>
+where
+    R: OverlapsRange<Q, C>,
+    C: Collate,
+{
+    let indices = overlapping_indices(ranges, query, collator);
+
+    if indices.is_empty() {
+        None
+    } else {
+        Some(indices.start)
+    }
+}
+
+/// Return `true` if any of the `ranges` overlaps the given `query` range according to `collator`.
+/// See [`overlapping_indices`] for details.
+pub fn overlaps_any<R, Q, C>(ranges: &[R], query: &Q, collator: &C) -> bool
+where
+    R: OverlapsRange<Q, C>,
+    C: Collate,
+{
+    first_overlapping(ranges, query, collator).is_some()
+}
+
+/// Return `true` if every one of the `ranges` overlaps the given `query` range
+/// according to `collator`. See [`overlapping_indices`] for details.
+///
+/// Example:
+/// ```
+/// use collate::{overlaps_all, overlaps_any, Collator};
+///
+/// let ranges = [0..2, 2..4, 4..6];
+/// let collator = Collator::default();
+/// assert!(overlaps_all(&ranges, &(1..5), &collator));
+/// assert!(!overlaps_all(&ranges, &(1..4), &collator));
+/// assert!(overlaps_any(&ranges, &(1..4), &collator));
+/// ```
+pub fn overlaps_all<R, Q, C>(ranges: &[R], query: &Q, collator: &C) -> bool
+where
+    R: OverlapsRange<Q, C>,
+    C: Collate,
+{
+    overlapping_indices(ranges, query, collator) == (0..ranges.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_overlapping_indices() {
+        let collator = Collator::<u32>::default();
+        let ranges = [
+            (Bound::Unbounded, Bound::Excluded(10)),
+            (Bound::Included(10), Bound::Included(20)),
+            (Bound::Excluded(20), Bound::Excluded(30)),
+            (Bound::Included(30), Bound::Unbounded),
+        ];
+
+        assert_eq!(overlapping_indices(&ranges, &(20..=20), &collator), 1..2);
+        assert_eq!(overlapping_indices(&ranges, &(15..=25), &collator), 1..3);
+        assert_eq!(overlapping_indices(&ranges, &(..), &collator), 0..4);
+        assert_eq!(first_overlapping(&ranges, &(30..), &collator), Some(3));
+        assert_eq!(first_overlapping(&ranges[..3], &(30..), &collator), None);
+    }
+}
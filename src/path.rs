@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{Collate, CollateRef};
+
+/// A collator for file system paths, which compares paths component by component,
+/// so that e.g. `a/b` collates before `a-b` and a directory collates before its contents.
+///
+/// Optionally, components can be compared case-insensitively, and entries which a given
+/// predicate identifies as directories can be collated before their sibling files.
+///
+/// Two [`PathCollator`]s are equal if they are both case-insensitive (or not) and both sort
+/// directories first (or not); the directory predicates themselves are not compared.
+///
+/// Example:
+/// ```
+/// use std::path::Path;
+/// use collate::{CollateRef, PathCollator};
+///
+/// let collator = PathCollator::new();
+///
+/// let mut paths = [Path::new("a-b"), Path::new("a/b"), Path::new("a")];
+/// paths.sort_by(|l, r| collator.cmp_ref(*l, *r));
+/// assert_eq!(paths, [Path::new("a"), Path::new("a/b"), Path::new("a-b")]);
+///
+/// let collator = PathCollator::new()
+///     .case_insensitive(true)
+///     .dirs_first(|path: &Path| path.extension().is_none());
+///
+/// let mut paths = [Path::new("B.txt"), Path::new("a.txt"), Path::new("src/lib.rs")];
+/// paths.sort_by(|l, r| collator.cmp_ref(*l, *r));
+/// assert_eq!(paths, [Path::new("src/lib.rs"), Path::new("a.txt"), Path::new("B.txt")]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct PathCollator<F = fn(&Path) -> bool> {
+    case_insensitive: bool,
+    is_dir: Option<F>,
+}
+
+impl PathCollator {
+    /// Construct a new case-sensitive [`PathCollator`] which does not sort directories first.
+    pub fn new() -> Self {
+        Self {
+            case_insensitive: false,
+            is_dir: None,
+        }
+    }
+}
+
+impl Default for PathCollator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> PathCollator<F> {
+    /// Set whether to compare path components case-insensitively.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Collate directories before their sibling files, using the given predicate to determine
+    /// whether a path is a directory.
+    ///
+    /// The predicate is called with the entry at which two paths diverge (e.g. `a/b` for
+    /// `a/b/c` and `a/d`), so whether an entry is a directory depends only on the entry itself.
+    /// It should return `true` for every entry with descendants, or those descendants will not
+    /// collate with their parent directory's siblings as expected.
+    pub fn dirs_first<D>(self, is_dir: D) -> PathCollator<D>
+    where
+        D: Fn(&Path) -> bool,
+    {
+        PathCollator {
+            case_insensitive: self.case_insensitive,
+            is_dir: Some(is_dir),
+        }
+    }
+
+    fn cmp_component(&self, left: &Component, right: &Component) -> Ordering {
+        if self.case_insensitive {
+            let left = left.as_os_str().to_string_lossy();
+            let right = right.as_os_str().to_string_lossy();
+
+            left.chars()
+                .flat_map(char::to_lowercase)
+                .cmp(right.chars().flat_map(char::to_lowercase))
+        } else {
+            left.cmp(right)
+        }
+    }
+}
+
+impl<F> fmt::Debug for PathCollator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PathCollator")
+            .field("case_insensitive", &self.case_insensitive)
+            .field("dirs_first", &self.is_dir.is_some())
+            .finish()
+    }
+}
+
+impl<F> PartialEq for PathCollator<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.case_insensitive == other.case_insensitive
+            && self.is_dir.is_some() == other.is_dir.is_some()
+    }
+}
+
+impl<F> Eq for PathCollator<F> {}
+
+impl<F: Fn(&Path) -> bool> Collate for PathCollator<F> {
+    type Value = PathBuf;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_path(), right.as_path())
+    }
+}
+
+impl<F: Fn(&Path) -> bool> CollateRef<Path> for PathCollator<F> {
+    fn cmp_ref(&self, left: &Path, right: &Path) -> Ordering {
+        let mut l_components = left.components();
+        let mut r_components = right.components();
+        let mut depth = 0;
+
+        loop {
+            let (l, r) = match (l_components.next(), r_components.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(l), Some(r)) => (l, r),
+            };
+
+            depth += 1;
+
+            let order = self.cmp_component(&l, &r);
+            if order == Ordering::Equal {
+                continue;
+            }
+
+            if let Some(is_dir) = &self.is_dir {
+                // the paths diverge here, so compare the entries at this depth
+                let entry_is_dir =
+                    |path: &Path| is_dir(&path.components().take(depth).collect::<PathBuf>());
+
+                let l_dir = entry_is_dir(left);
+                let r_dir = entry_is_dir(right);
+
+                if l_dir != r_dir {
+                    return r_dir.cmp(&l_dir);
+                }
+            }
+
+            return order;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::check_collate;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_path_collator() {
+        let dirs = ["docs", "src", "src/stream"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect::<HashSet<PathBuf>>();
+
+        let collator = PathCollator::new().dirs_first(|path: &Path| dirs.contains(path));
+
+        let mut paths = vec![
+            "src/lib.rs",
+            "README.md",
+            "src/stream/mod.rs",
+            "docs",
+            "Cargo.toml",
+            "src",
+            "src/stream",
+        ];
+
+        paths.sort_by(|l, r| collator.cmp_ref(Path::new(l), Path::new(r)));
+
+        assert_eq!(
+            paths,
+            vec![
+                "docs",
+                "src",
+                "src/stream",
+                "src/stream/mod.rs",
+                "src/lib.rs",
+                "Cargo.toml",
+                "README.md",
+            ]
+        );
+
+        let collator = PathCollator::new().case_insensitive(true);
+        assert_eq!(
+            collator.cmp_ref(Path::new("Src/LIB.rs"), Path::new("src/lib.rs")),
+            Ordering::Equal
+        );
+
+        assert_eq!(
+            collator.cmp_ref(Path::new("a/b"), Path::new("a-b")),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_path_collator_dir_with_extension() {
+        let samples = ["v1.0", "v1.0/x", "a.txt", "b"].map(PathBuf::from);
+
+        // "v1.0" is treated as a file, even though it has a descendant
+        let collator = PathCollator::new().dirs_first(|path: &Path| path.extension().is_none());
+        assert_eq!(check_collate(&collator, &samples), Ok(()));
+
+        let mut paths = samples.clone();
+        paths.sort_by(|l, r| collator.cmp(l, r));
+        assert_eq!(paths, ["b", "a.txt", "v1.0", "v1.0/x"].map(PathBuf::from));
+
+        let collator = PathCollator::new()
+            .dirs_first(|path: &Path| path.extension().is_none() || path == Path::new("v1.0"));
+        assert_eq!(check_collate(&collator, &samples), Ok(()));
+
+        paths.sort_by(|l, r| collator.cmp(l, r));
+        assert_eq!(paths, ["b", "v1.0", "v1.0/x", "a.txt"].map(PathBuf::from));
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::ops::Bound;
+
+use crate::str_prefix_successor;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Token {
+    Literal(char),
+    One,
+    Many,
+}
+
+/// A simple string pattern with single-character and multi-character wildcards,
+/// such as a SQL `LIKE` pattern or a glob.
+///
+/// A pattern with a literal prefix can be converted into a covering [`Pattern::range`]
+/// in order to use a collated index to find matching strings, plus a [`Pattern::residual`]
+/// pattern to filter the strings in that range.
+/// The covering range is only valid for strings collated by code point,
+/// e.g. using a [`Collator<String>`](crate::Collator).
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::Pattern;
+///
+/// let pattern = Pattern::glob("img_??.png");
+/// assert_eq!(
+///     pattern.range(),
+///     (Bound::Included("img_".to_string()), Bound::Excluded("img`".to_string()))
+/// );
+///
+/// let residual = pattern.residual().expect("residual");
+/// assert!(residual.matches("01.png"));
+/// assert!(pattern.matches("img_01.png"));
+/// assert!(!pattern.matches("img_001.png"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    tokens: Vec<Token>,
+}
+
+impl Pattern {
+    /// Parse a SQL `LIKE` pattern, where `%` matches any sequence of characters,
+    /// `_` matches any single character, and `\` escapes the following character.
+    pub fn like(pattern: &str) -> Self {
+        Self::parse(pattern, '_', '%')
+    }
+
+    /// Parse a glob pattern, where `*` matches any sequence of characters,
+    /// `?` matches any single character, and `\` escapes the following character.
+    /// Character classes like `[a-z]` are not supported.
+    pub fn glob(pattern: &str) -> Self {
+        Self::parse(pattern, '?', '*')
+    }
+
+    fn parse(pattern: &str, one: char, many: char) -> Self {
+        let mut tokens = Vec::with_capacity(pattern.len());
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            let token = if c == '\\' {
+                Token::Literal(chars.next().unwrap_or(c))
+            } else if c == one {
+                Token::One
+            } else if c == many {
+                if tokens.last() == Some(&Token::Many) {
+                    continue;
+                }
+
+                Token::Many
+            } else {
+                Token::Literal(c)
+            };
+
+            tokens.push(token);
+        }
+
+        Self { tokens }
+    }
+
+    /// Return the literal prefix of this pattern.
+    pub fn prefix(&self) -> String {
+        self.tokens
+            .iter()
+            .map_while(|token| match token {
+                Token::Literal(c) => Some(*c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Return `true` if this pattern contains no wildcards.
+    pub fn is_literal(&self) -> bool {
+        self.tokens
+            .iter()
+            .all(|token| matches!(token, Token::Literal(_)))
+    }
+
+    /// Return the narrowest range of strings which covers every string matching this pattern.
+    pub fn range(&self) -> (Bound<String>, Bound<String>) {
+        let prefix = self.prefix();
+
+        if self.is_literal() {
+            (Bound::Included(prefix.clone()), Bound::Included(prefix))
+        } else if prefix.is_empty() {
+            (Bound::Unbounded, Bound::Unbounded)
+        } else {
+            let end = match str_prefix_successor(&prefix) {
+                Some(end) => Bound::Excluded(end),
+                None => Bound::Unbounded,
+            };
+
+            (Bound::Included(prefix), end)
+        }
+    }
+
+    /// Return the part of this pattern following its literal prefix,
+    /// or `None` if every string in this pattern's [`Pattern::range`] matches this pattern.
+    pub fn residual(&self) -> Option<Self> {
+        let prefix_len = self
+            .tokens
+            .iter()
+            .take_while(|token| matches!(token, Token::Literal(_)))
+            .count();
+
+        match &self.tokens[prefix_len..] {
+            [] | [Token::Many] => None,
+            tokens => Some(Self {
+                tokens: tokens.to_vec(),
+            }),
+        }
+    }
+
+    /// Return `true` if the given `value` matches this pattern.
+    pub fn matches(&self, value: &str) -> bool {
+        let value = value.chars().collect::<Vec<char>>();
+        let tokens = &self.tokens;
+
+        let (mut t, mut v) = (0, 0);
+
+        // the position of the last multi-character wildcard and the value position it matched
+        let mut backtrack = None;
+
+        while v < value.len() {
+            match tokens.get(t) {
+                Some(Token::Literal(c)) if *c == value[v] => {
+                    t += 1;
+                    v += 1;
+                }
+                Some(Token::One) => {
+                    t += 1;
+                    v += 1;
+                }
+                Some(Token::Many) => {
+                    backtrack = Some((t, v));
+                    t += 1;
+                }
+                _ => match backtrack {
+                    Some((many, matched)) => {
+                        // let the last multi-character wildcard match one more character
+                        t = many + 1;
+                        v = matched + 1;
+                        backtrack = Some((many, v));
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        tokens[t..].iter().all(|token| *token == Token::Many)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // format as a SQL LIKE pattern, regardless of the syntax this pattern was parsed from
+        for token in &self.tokens {
+            match token {
+                Token::Literal(c) if matches!(c, '%' | '_' | '\\') => write!(f, "\\{c}")?,
+                Token::Literal(c) => write!(f, "{c}")?,
+                Token::One => f.write_str("_")?,
+                Token::Many => f.write_str("%")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like() {
+        let pattern = Pattern::like("abc%");
+        assert_eq!(pattern.prefix(), "abc");
+        assert_eq!(pattern.residual(), None);
+        assert_eq!(
+            pattern.range(),
+            (
+                Bound::Included("abc".to_string()),
+                Bound::Excluded("abd".to_string())
+            )
+        );
+
+        let pattern = Pattern::like("a\\%b_%c");
+        assert_eq!(pattern.prefix(), "a%b");
+        assert_eq!(pattern.to_string(), "a\\%b_%c");
+        assert!(pattern.matches("a%bxc"));
+        assert!(pattern.matches("a%bxyzc"));
+        assert!(!pattern.matches("a%bc"));
+        assert!(!pattern.matches("axbyc"));
+
+        let pattern = Pattern::like("exact");
+        assert!(pattern.is_literal());
+        assert_eq!(
+            pattern.range(),
+            (
+                Bound::Included("exact".to_string()),
+                Bound::Included("exact".to_string())
+            )
+        );
+
+        let pattern = Pattern::like("%suffix");
+        assert_eq!(pattern.range(), (Bound::Unbounded, Bound::Unbounded));
+        assert!(pattern.matches("a suffix"));
+        assert!(!pattern.matches("suffix?"));
+    }
+}
@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::Collate;
+
+/// A collator for integer keys which reorders the key space according to a permutation
+/// or a ranking function, e.g. to collate keys by an externally computed popularity rank.
+///
+/// A [`PermutationCollator`] constructed from a permutation collates the keys in the order
+/// they appear in the permutation, followed by any greater keys in their natural order.
+/// A [`PermutationCollator`] constructed from a ranking function collates keys by rank,
+/// and keys of equal rank in their natural order, so that only equal keys collate equal.
+///
+/// Two [`PermutationCollator`]s are equal if they have the same permutation (or both use
+/// a ranking function); the ranking functions themselves are not compared.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, PermutationCollator};
+///
+/// let collator = PermutationCollator::new(vec![2, 0, 1]);
+///
+/// let mut keys = vec![4, 0, 1, 2, 3];
+/// keys.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(keys, [2, 0, 1, 3, 4]);
+///
+/// let popularity = [10, 50, 30];
+/// let collator = PermutationCollator::by_rank(|key: usize| usize::MAX - popularity[key]);
+///
+/// let mut keys = vec![0, 1, 2];
+/// keys.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(keys, [1, 2, 0]);
+/// ```
+#[derive(Clone)]
+pub struct PermutationCollator<F = fn(usize) -> usize> {
+    ranks: Vec<usize>,
+    rank: Option<F>,
+}
+
+impl PermutationCollator {
+    /// Construct a new [`PermutationCollator`] which collates the keys `0..permutation.len()`
+    /// in the order given by `permutation`.
+    ///
+    /// Panics: if `permutation` is not a permutation of the keys `0..permutation.len()`.
+    pub fn new(permutation: Vec<usize>) -> Self {
+        let mut ranks = vec![usize::MAX; permutation.len()];
+
+        for (rank, key) in permutation.into_iter().enumerate() {
+            assert!(
+                key < ranks.len() && ranks[key] == usize::MAX,
+                "invalid permutation: key {key} is out of range or repeated"
+            );
+
+            ranks[key] = rank;
+        }
+
+        Self { ranks, rank: None }
+    }
+
+    /// Construct a new [`PermutationCollator`] which collates keys by the given `rank` function.
+    pub fn by_rank<R>(rank: R) -> PermutationCollator<R>
+    where
+        R: Fn(usize) -> usize,
+    {
+        PermutationCollator {
+            ranks: Vec::new(),
+            rank: Some(rank),
+        }
+    }
+}
+
+impl<F: Fn(usize) -> usize> PermutationCollator<F> {
+    /// Return the rank of the given `key`.
+    pub fn rank(&self, key: usize) -> usize {
+        if let Some(rank) = &self.rank {
+            rank(key)
+        } else {
+            self.ranks.get(key).copied().unwrap_or(key)
+        }
+    }
+}
+
+impl<F> fmt::Debug for PermutationCollator<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.rank.is_some() {
+            f.write_str("PermutationCollator by rank")
+        } else {
+            f.debug_tuple("PermutationCollator")
+                .field(&self.ranks)
+                .finish()
+        }
+    }
+}
+
+impl<F> PartialEq for PermutationCollator<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranks == other.ranks && self.rank.is_some() == other.rank.is_some()
+    }
+}
+
+impl<F> Eq for PermutationCollator<F> {}
+
+impl<F: Fn(usize) -> usize> Collate for PermutationCollator<F> {
+    type Value = usize;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        // keys beyond the permutation rank as themselves, after all the keys in the permutation
+        self.rank(*left)
+            .cmp(&self.rank(*right))
+            .then_with(|| left.cmp(right))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutation_collator() {
+        let collator = PermutationCollator::new(vec![1, 3, 0, 2]);
+        assert_eq!(collator.rank(3), 1);
+        assert_eq!(collator.rank(7), 7);
+        assert_eq!(collator.cmp(&2, &0), Ordering::Greater);
+        assert_eq!(collator.cmp(&2, &4), Ordering::Less);
+        assert_eq!(collator, PermutationCollator::new(vec![1, 3, 0, 2]));
+
+        let collator = PermutationCollator::by_rank(|key| key % 3);
+        let mut keys = (0..7).collect::<Vec<usize>>();
+        keys.sort_by(|l, r| collator.cmp(l, r));
+        assert_eq!(keys, [0, 3, 6, 1, 4, 2, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_permutation() {
+        PermutationCollator::new(vec![0, 2, 2]);
+    }
+}
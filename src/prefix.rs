@@ -0,0 +1,54 @@
+/// Return the shortest byte string which is greater than every byte string
+/// starting with the given `prefix`, or `None` if there is no such byte string
+/// (i.e. if the `prefix` is empty or consists only of `0xFF` bytes).
+///
+/// This is the exclusive upper bound of a range covering every key with the given `prefix`.
+///
+/// Examples:
+/// ```
+/// use collate::prefix_successor;
+/// assert_eq!(prefix_successor(b"abc"), Some(b"abd".to_vec()));
+/// assert_eq!(prefix_successor(&[1, 0xFF, 0xFF]), Some(vec![2]));
+/// assert_eq!(prefix_successor(&[0xFF]), None);
+/// assert_eq!(prefix_successor(&[]), None);
+/// ```
+pub fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let len = prefix.iter().rposition(|byte| *byte != u8::MAX)?;
+
+    let mut successor = prefix[..=len].to_vec();
+    successor[len] += 1;
+    Some(successor)
+}
+
+/// Return the shortest string which is greater (by code point) than every string
+/// starting with the given `prefix`, or `None` if there is no such string
+/// (i.e. if the `prefix` is empty or consists only of [`char::MAX`]).
+///
+/// The surrogate code points, which are not valid `char`s, are skipped.
+///
+/// Examples:
+/// ```
+/// use collate::str_prefix_successor;
+/// assert_eq!(str_prefix_successor("abc"), Some("abd".to_string()));
+/// assert_eq!(str_prefix_successor("a\u{10FFFF}"), Some("b".to_string()));
+/// assert_eq!(str_prefix_successor("\u{D7FF}"), Some("\u{E000}".to_string()));
+/// assert_eq!(str_prefix_successor(""), None);
+/// ```
+pub fn str_prefix_successor(prefix: &str) -> Option<String> {
+    let mut successor = prefix.to_string();
+
+    while let Some(c) = successor.pop() {
+        let next = match c {
+            char::MAX => continue,
+            '\u{D7FF}' => Some('\u{E000}'),
+            c => char::from_u32(c as u32 + 1),
+        };
+
+        if let Some(next) = next {
+            successor.push(next);
+            return Some(successor);
+        }
+    }
+
+    None
+}
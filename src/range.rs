@@ -0,0 +1,606 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::{cmp_bound, CollateRef, Predecessor, Successor};
+
+pub(crate) type Bounds<V> = (Bound<V>, Bound<V>);
+
+/// Return `true` if the given `range` contains no values according to `collator`,
+/// e.g. `(Bound::Excluded(1), Bound::Excluded(1))` or `(Bound::Included(2), Bound::Included(1))`.
+///
+/// For a discrete type, a range like `(Bound::Excluded(1), Bound::Excluded(2))` is also empty,
+/// but this is not detected without a [`Successor`].
+pub fn range_is_empty<V, C>(collator: &C, range: &Bounds<V>) -> bool
+where
+    C: CollateRef<V>,
+{
+    match (&range.0, &range.1) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+        (Bound::Included(start), Bound::Included(end)) => {
+            collator.cmp_ref(start, end) == Ordering::Greater
+        }
+        (Bound::Included(start) | Bound::Excluded(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end)) => {
+            collator.cmp_ref(start, end) != Ordering::Less
+        }
+    }
+}
+
+/// Return the union of the `left` and `right` ranges according to `collator`,
+/// or `None` if they neither overlap nor touch, i.e. if the union is not a single range.
+///
+/// Two ranges touch if one ends where the other starts and the boundary value is included
+/// in at least one of them, e.g. `1..3` and `3..5`. To treat ranges of a discrete type like
+/// `1..=2` and `3..=5` as adjacent, use [`discrete_range_union`].
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{range_union, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let left = (Bound::Included(1), Bound::Excluded(3));
+/// let right = (Bound::Included(3), Bound::Unbounded);
+/// assert_eq!(range_union(&collator, &left, &right), Some((Bound::Included(1), Bound::Unbounded)));
+///
+/// let right = (Bound::Excluded(3), Bound::Unbounded);
+/// assert_eq!(range_union(&collator, &left, &right), None);
+/// ```
+pub fn range_union<V, C>(collator: &C, left: &Bounds<V>, right: &Bounds<V>) -> Option<Bounds<V>>
+where
+    V: Clone,
+    C: CollateRef<V>,
+{
+    if range_is_empty(collator, left) {
+        return Some(right.clone());
+    } else if range_is_empty(collator, right) {
+        return Some(left.clone());
+    }
+
+    if touches(collator, left.1.as_ref(), right.0.as_ref())
+        && touches(collator, right.1.as_ref(), left.0.as_ref())
+    {
+        Some(hull(collator, left, right))
+    } else {
+        None
+    }
+}
+
+/// Return the union of the `left` and `right` ranges of a discrete type according to `collator`,
+/// or `None` if they are neither overlapping nor adjacent.
+///
+/// Unlike [`range_union`], this treats ranges like `1..=2` and `3..=5` as adjacent,
+/// because there is no value between `2` and its [`Successor`] `3`.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{discrete_range_union, range_union, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let left = (Bound::Included(1), Bound::Included(2));
+/// let right = (Bound::Included(3), Bound::Included(5));
+/// assert_eq!(range_union(&collator, &left, &right), None);
+/// assert_eq!(
+///     discrete_range_union(&collator, &left, &right),
+///     Some((Bound::Included(1), Bound::Included(5)))
+/// );
+/// ```
+pub fn discrete_range_union<C>(
+    collator: &C,
+    left: &Bounds<C::Value>,
+    right: &Bounds<C::Value>,
+) -> Option<Bounds<C::Value>>
+where
+    C: Successor,
+    C::Value: Clone,
+{
+    if range_is_empty(collator, left) {
+        return Some(right.clone());
+    } else if range_is_empty(collator, right) {
+        return Some(left.clone());
+    }
+
+    // an included end bound touches an included start bound at its successor
+    let adjacent = |end: &Bound<C::Value>, start: &Bound<C::Value>| match (end, start) {
+        (Bound::Included(end), Bound::Included(start)) => collator.is_adjacent(end, start),
+        _ => false,
+    };
+
+    let left_touches = touches(collator, left.1.as_ref(), right.0.as_ref());
+    let right_touches = touches(collator, right.1.as_ref(), left.0.as_ref());
+
+    if (left_touches || adjacent(&left.1, &right.0))
+        && (right_touches || adjacent(&right.1, &left.0))
+    {
+        Some(hull(collator, left, right))
+    } else {
+        None
+    }
+}
+
+/// Return the ranges which cover the values in the `left` range but not in the `right` range
+/// according to `collator`, in collation order. There are zero, one, or two such ranges.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{range_difference, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let requested = (Bound::Included(0), Bound::Excluded(100));
+/// let fetched = (Bound::Included(20), Bound::Included(50));
+///
+/// assert_eq!(
+///     range_difference(&collator, &requested, &fetched),
+///     vec![
+///         (Bound::Included(0), Bound::Excluded(20)),
+///         (Bound::Excluded(50), Bound::Excluded(100)),
+///     ]
+/// );
+///
+/// assert_eq!(range_difference(&collator, &fetched, &requested), vec![]);
+/// ```
+pub fn range_difference<V, C>(collator: &C, left: &Bounds<V>, right: &Bounds<V>) -> Vec<Bounds<V>>
+where
+    V: Clone,
+    C: CollateRef<V>,
+{
+    if range_is_empty(collator, left) {
+        return vec![];
+    }
+
+    if range_is_empty(collator, &intersection(collator, left, right)) {
+        return vec![left.clone()];
+    }
+
+    let before = complement(&right.0).map(|end| (left.0.clone(), end));
+    let after = complement(&right.1).map(|start| (start, left.1.clone()));
+
+    [before, after]
+        .into_iter()
+        .flatten()
+        .filter(|range| !range_is_empty(collator, range))
+        .collect()
+}
+
+/// Return the range of values between the `left` and `right` ranges according to `collator`,
+/// or `None` if they overlap or touch (or either is empty), i.e. if there is no such value.
+///
+/// The two ranges may be given in either order.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{range_gap, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// let cached = (Bound::Included(50), Bound::Unbounded);
+/// let fetched = (Bound::Included(0), Bound::Included(20));
+///
+/// assert_eq!(
+///     range_gap(&collator, &cached, &fetched),
+///     Some((Bound::Excluded(20), Bound::Excluded(50)))
+/// );
+///
+/// let fetched = (Bound::Included(0), Bound::Excluded(50));
+/// assert_eq!(range_gap(&collator, &cached, &fetched), None);
+/// ```
+pub fn range_gap<V, C>(collator: &C, left: &Bounds<V>, right: &Bounds<V>) -> Option<Bounds<V>>
+where
+    V: Clone,
+    C: CollateRef<V>,
+{
+    if range_is_empty(collator, left) || range_is_empty(collator, right) {
+        return None;
+    }
+
+    let (first, second) = match cmp_start(collator, left.0.as_ref(), right.0.as_ref()) {
+        Ordering::Greater => (right, left),
+        _ => (left, right),
+    };
+
+    if touches(collator, first.1.as_ref(), second.0.as_ref()) {
+        None
+    } else {
+        // neither bound is unbounded, since the ranges would otherwise touch
+        Some((complement(&first.1)?, complement(&second.0)?))
+    }
+}
+
+/// Coalesce the given `ranges`, which **must** be sorted by their start bounds according to
+/// `collator`, into the minimal collated sequence of disjoint ranges which covers them,
+/// by merging ranges which overlap or touch (as in [`range_union`]). Empty ranges are skipped.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{coalesce_ranges, Collator};
+///
+/// let shards = [
+///     (Bound::Included(0), Bound::Excluded(10)),
+///     (Bound::Included(5), Bound::Excluded(20)),
+///     (Bound::Included(20), Bound::Included(30)),
+///     (Bound::Excluded(30), Bound::Excluded(40)),
+///     (Bound::Included(50), Bound::Unbounded),
+/// ];
+///
+/// let coalesced = coalesce_ranges(Collator::<u32>::default(), shards).collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     coalesced,
+///     [
+///         (Bound::Included(0), Bound::Excluded(40)),
+///         (Bound::Included(50), Bound::Unbounded),
+///     ]
+/// );
+/// ```
+pub fn coalesce_ranges<V, C, I>(collator: C, ranges: I) -> CoalesceRanges<V, C, I::IntoIter>
+where
+    V: Clone,
+    C: CollateRef<V>,
+    I: IntoIterator<Item = Bounds<V>>,
+{
+    CoalesceRanges {
+        collator,
+        source: ranges.into_iter(),
+        pending: None,
+    }
+}
+
+/// An iterator over coalesced ranges, returned by [`coalesce_ranges`].
+pub struct CoalesceRanges<V, C, I> {
+    collator: C,
+    source: I,
+    pending: Option<Bounds<V>>,
+}
+
+impl<V, C, I> CoalesceRanges<V, C, I>
+where
+    C: CollateRef<V>,
+    I: Iterator<Item = Bounds<V>>,
+{
+    fn next_non_empty(&mut self) -> Option<Bounds<V>> {
+        let collator = &self.collator;
+        self.source.find(|range| !range_is_empty(collator, range))
+    }
+}
+
+impl<V, C, I> Iterator for CoalesceRanges<V, C, I>
+where
+    V: Clone,
+    C: CollateRef<V>,
+    I: Iterator<Item = Bounds<V>>,
+{
+    type Item = Bounds<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut coalesced = match self.pending.take() {
+            Some(range) => range,
+            None => self.next_non_empty()?,
+        };
+
+        while let Some(range) = self.next_non_empty() {
+            debug_assert_ne!(
+                cmp_start(&self.collator, coalesced.0.as_ref(), range.0.as_ref()),
+                Ordering::Greater,
+                "ranges to coalesce are not sorted by start bound"
+            );
+
+            if touches(&self.collator, coalesced.1.as_ref(), range.0.as_ref()) {
+                coalesced = hull(&self.collator, &coalesced, &range);
+            } else {
+                self.pending = Some(range);
+                break;
+            }
+        }
+
+        Some(coalesced)
+    }
+}
+
+// return the range of values in both `left` and `right`, which may be empty
+pub(crate) fn intersection<V, C>(collator: &C, left: &Bounds<V>, right: &Bounds<V>) -> Bounds<V>
+where
+    V: Clone,
+    C: CollateRef<V>,
+{
+    let start = match cmp_start(collator, left.0.as_ref(), right.0.as_ref()) {
+        Ordering::Less => right.0.clone(),
+        _ => left.0.clone(),
+    };
+
+    let end = match cmp_end(collator, left.1.as_ref(), right.1.as_ref()) {
+        Ordering::Greater => right.1.clone(),
+        _ => left.1.clone(),
+    };
+
+    (start, end)
+}
+
+// return the bound on the other side of the given `bound`, e.g. to end a range where another
+// range starts, or `None` if there are no values on the other side of an unbounded bound
+fn complement<V: Clone>(bound: &Bound<V>) -> Option<Bound<V>> {
+    match bound {
+        Bound::Included(value) => Some(Bound::Excluded(value.clone())),
+        Bound::Excluded(value) => Some(Bound::Included(value.clone())),
+        Bound::Unbounded => None,
+    }
+}
+
+// return the smallest range which contains both `left` and `right`
+pub(crate) fn hull<V, C>(collator: &C, left: &Bounds<V>, right: &Bounds<V>) -> Bounds<V>
+where
+    V: Clone,
+    C: CollateRef<V>,
+{
+    let start = match cmp_start(collator, left.0.as_ref(), right.0.as_ref()) {
+        Ordering::Greater => right.0.clone(),
+        _ => left.0.clone(),
+    };
+
+    let end = match cmp_end(collator, left.1.as_ref(), right.1.as_ref()) {
+        Ordering::Less => right.1.clone(),
+        _ => left.1.clone(),
+    };
+
+    (start, end)
+}
+
+// compare two start bounds, where an unbounded start is the least
+pub(crate) fn cmp_start<V, C>(collator: &C, left: Bound<&V>, right: Bound<&V>) -> Ordering
+where
+    C: CollateRef<V>,
+{
+    cmp_bound(collator, left, right, Ordering::Greater, Ordering::Less)
+}
+
+// compare two end bounds, where an unbounded end is the greatest
+pub(crate) fn cmp_end<V, C>(collator: &C, left: Bound<&V>, right: Bound<&V>) -> Ordering
+where
+    C: CollateRef<V>,
+{
+    cmp_bound(collator, left, right, Ordering::Less, Ordering::Greater)
+}
+
+// return `true` if there is no value between the `end` of one range and the `start` of another,
+// i.e. if the two ranges overlap or touch
+pub(crate) fn touches<V, C>(collator: &C, end: Bound<&V>, start: Bound<&V>) -> bool
+where
+    C: CollateRef<V>,
+{
+    match (end, start) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Excluded(end), Bound::Excluded(start)) => {
+            collator.cmp_ref(end, start) == Ordering::Greater
+        }
+        (
+            Bound::Included(end) | Bound::Excluded(end),
+            Bound::Included(start) | Bound::Excluded(start),
+        ) => collator.cmp_ref(end, start) != Ordering::Less,
+    }
+}
+
+/// Return the canonical form of the given `range` of a discrete type, or `None` if it's empty,
+/// so that ranges which contain the same values are equal, e.g. to deduplicate cached ranges.
+///
+/// An excluded bound is converted to an included bound where possible,
+/// and a bound which includes the least or greatest value is converted to an unbounded bound.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{normalize_range, Collator};
+///
+/// let collator = Collator::<u8>::default();
+///
+/// let range = (Bound::Excluded(1), Bound::Excluded(5));
+/// assert_eq!(normalize_range(&collator, range), Some((Bound::Included(2), Bound::Included(4))));
+///
+/// let range = (Bound::Included(0), Bound::Excluded(u8::MAX));
+/// assert_eq!(normalize_range(&collator, range), Some((Bound::Unbounded, Bound::Included(254))));
+///
+/// let range = (Bound::Excluded(1), Bound::Excluded(2));
+/// assert_eq!(normalize_range(&collator, range), None);
+/// ```
+pub fn normalize_range<C>(collator: &C, range: Bounds<C::Value>) -> Option<Bounds<C::Value>>
+where
+    C: Successor + Predecessor,
+{
+    let start = match collator.include_start(range.0)? {
+        Bound::Included(start) if collator.prev_value(&start).is_none() => Bound::Unbounded,
+        start => start,
+    };
+
+    let end = match collator.include_end(range.1)? {
+        Bound::Included(end) if collator.next_value(&end).is_none() => Bound::Unbounded,
+        end => end,
+    };
+
+    let range = (start, end);
+
+    if range_is_empty(collator, &range) {
+        None
+    } else {
+        Some(range)
+    }
+}
+
+/// Iterate over every value in the given `range` of a discrete type, in collation order,
+/// e.g. to generate test data or expand a small range into its values.
+///
+/// Returns `None` if the start of the `range` is unbounded, since there is no first value
+/// to start from.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{iter_range, Collator, DirectedCollator, Direction};
+///
+/// let collator = Collator::<u8>::default();
+/// let range = (Bound::Excluded(250), Bound::Unbounded);
+/// let values = iter_range(&collator, &range).expect("first value");
+/// assert_eq!(values.collect::<Vec<_>>(), [251, 252, 253, 254, 255]);
+///
+/// let range = (Bound::Unbounded, Bound::Included(3));
+/// assert!(iter_range(&collator, &range).is_none());
+///
+/// let collator = DirectedCollator::new(Collator::<char>::default(), Direction::Descending);
+/// let range = (Bound::Included('c'), Bound::Excluded('a'));
+/// let values = iter_range(&collator, &range).expect("first value");
+/// assert_eq!(values.collect::<String>(), "cb");
+/// ```
+pub fn iter_range<'a, C>(
+    collator: &'a C,
+    range: &'a Bounds<C::Value>,
+) -> Option<impl Iterator<Item = C::Value> + 'a>
+where
+    C: Successor,
+    C::Value: Clone,
+{
+    let first = match &range.0 {
+        Bound::Included(start) => Some(start.clone()),
+        Bound::Excluded(start) => collator.next_value(start),
+        Bound::Unbounded => return None,
+    };
+
+    let values = std::iter::successors(first, |value| collator.next_value(value))
+        .take_while(|value| !ends_before(collator, range.1.as_ref(), value));
+
+    Some(values)
+}
+
+// return `true` if there is a value in both the range with the given `end`
+// and the range with the given `start`, assuming the former does not start after the latter
+pub(crate) fn intersects<V, C>(collator: &C, end: Bound<&V>, start: Bound<&V>) -> bool
+where
+    C: CollateRef<V>,
+{
+    match (end, start) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Included(end), Bound::Included(start)) => {
+            collator.cmp_ref(end, start) != Ordering::Less
+        }
+        (
+            Bound::Included(end) | Bound::Excluded(end),
+            Bound::Included(start) | Bound::Excluded(start),
+        ) => collator.cmp_ref(end, start) == Ordering::Greater,
+    }
+}
+
+// return `true` if a range with the given `start` bound starts after the given `value`
+pub(crate) fn starts_after<V, C>(collator: &C, start: Bound<&V>, value: &V) -> bool
+where
+    C: CollateRef<V>,
+{
+    match start {
+        Bound::Included(start) => collator.cmp_ref(start, value) == Ordering::Greater,
+        Bound::Excluded(start) => collator.cmp_ref(start, value) != Ordering::Less,
+        Bound::Unbounded => false,
+    }
+}
+
+// return `true` if a range with the given `end` bound ends before the given `value`
+pub(crate) fn ends_before<V, C>(collator: &C, end: Bound<&V>, value: &V) -> bool
+where
+    C: CollateRef<V>,
+{
+    match end {
+        Bound::Included(end) => collator.cmp_ref(end, value) == Ordering::Less,
+        Bound::Excluded(end) => collator.cmp_ref(end, value) != Ordering::Greater,
+        Bound::Unbounded => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_coalesce_ranges() {
+        let collator = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+
+        let ranges = [
+            (Bound::Unbounded, Bound::Excluded(9)),
+            (Bound::Excluded(1), Bound::Excluded(1)),
+            (Bound::Included(9), Bound::Excluded(7)),
+            (Bound::Included(8), Bound::Included(6)),
+            (Bound::Excluded(6), Bound::Included(5)),
+            (Bound::Excluded(4), Bound::Included(2)),
+        ];
+
+        assert_eq!(
+            coalesce_ranges(collator, ranges).collect::<Vec<_>>(),
+            [
+                (Bound::Unbounded, Bound::Included(5)),
+                (Bound::Excluded(4), Bound::Included(2)),
+            ]
+        );
+
+        let ranges = [(Bound::Excluded(1), Bound::Excluded(1))];
+        assert_eq!(coalesce_ranges(collator, ranges).next(), None);
+    }
+
+    #[test]
+    fn test_range_difference() {
+        let collator = Collator::<u32>::default();
+
+        let left = (Bound::Included(1), Bound::Unbounded);
+        let right = (Bound::Unbounded, Bound::Excluded(4));
+        assert_eq!(
+            range_difference(&collator, &left, &right),
+            vec![(Bound::Included(4), Bound::Unbounded)]
+        );
+
+        let right = (Bound::Excluded(4), Bound::Excluded(5));
+        assert_eq!(range_difference(&collator, &left, &right).len(), 2);
+
+        let right = (Bound::Unbounded, Bound::Excluded(1));
+        assert_eq!(range_difference(&collator, &left, &right), vec![left]);
+    }
+
+    #[test]
+    fn test_range_gap() {
+        let collator = DirectedCollator::new(Collator::<u32>::default(), Direction::Descending);
+
+        let left = (Bound::Included(3), Bound::Unbounded);
+        let right = (Bound::Unbounded, Bound::Included(5));
+        assert_eq!(
+            range_gap(&collator, &left, &right),
+            Some((Bound::Excluded(5), Bound::Excluded(3)))
+        );
+
+        let right = (Bound::Unbounded, Bound::Excluded(3));
+        assert_eq!(range_gap(&collator, &left, &right), None);
+
+        let right = (Bound::Unbounded, Bound::Included(1));
+        assert_eq!(range_gap(&collator, &left, &right), None);
+
+        let empty = (Bound::Excluded(9), Bound::Excluded(9));
+        assert_eq!(range_gap(&collator, &left, &empty), None);
+    }
+
+    #[test]
+    fn test_range_union() {
+        let collator = Collator::<u32>::default();
+
+        let left = (Bound::Included(1), Bound::Included(5));
+        let right = (Bound::Excluded(5), Bound::Included(8));
+        assert_eq!(
+            range_union(&collator, &left, &right),
+            Some((Bound::Included(1), Bound::Included(8)))
+        );
+
+        let empty = (Bound::Excluded(9), Bound::Excluded(9));
+        assert_eq!(range_union(&collator, &left, &empty), Some(left));
+
+        let descending = DirectedCollator::new(collator, Direction::Descending);
+        let left = (Bound::Included(5), Bound::Included(3));
+        let right = (Bound::Included(2), Bound::Unbounded);
+        assert_eq!(
+            discrete_range_union(&descending, &left, &right),
+            Some((Bound::Included(5), Bound::Unbounded))
+        );
+    }
+}
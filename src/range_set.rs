@@ -0,0 +1,203 @@
+use std::ops::Bound;
+use std::slice;
+
+use crate::range::{hull, intersects, touches};
+use crate::{range_difference, range_is_empty, CollateRef, Overlap, OverlapsValue};
+
+/// A collated set of disjoint ranges of values of type `V`, which coalesces overlapping and
+/// touching ranges as they're inserted, e.g. to track which key ranges of a B-Tree or a cache
+/// have been loaded.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, RangeSet};
+///
+/// let mut set = RangeSet::new(Collator::<u32>::default());
+/// set.insert((Bound::Included(0), Bound::Excluded(10)));
+/// set.insert((Bound::Included(20), Bound::Excluded(30)));
+/// set.insert((Bound::Included(10), Bound::Excluded(15)));
+/// set.remove(&(Bound::Included(5), Bound::Included(7)));
+///
+/// assert_eq!(
+///     set.iter().cloned().collect::<Vec<_>>(),
+///     [
+///         (Bound::Included(0), Bound::Excluded(5)),
+///         (Bound::Excluded(7), Bound::Excluded(15)),
+///         (Bound::Included(20), Bound::Excluded(30)),
+///     ]
+/// );
+///
+/// assert!(set.contains_value(&12));
+/// assert!(!set.contains_value(&15));
+/// assert!(set.overlaps(&(Bound::Included(16), Bound::Included(20))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RangeSet<V, C> {
+    collator: C,
+    ranges: Vec<(Bound<V>, Bound<V>)>,
+}
+
+impl<V, C> RangeSet<V, C> {
+    /// Construct a new, empty [`RangeSet`] which collates its ranges using `collator`.
+    pub fn new(collator: C) -> Self {
+        Self {
+            collator,
+            ranges: Vec::new(),
+        }
+    }
+
+    /// Borrow the collator of this [`RangeSet`].
+    pub fn collator(&self) -> &C {
+        &self.collator
+    }
+
+    /// Return `true` if this [`RangeSet`] contains no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Return the number of disjoint ranges in this [`RangeSet`].
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Iterate over the disjoint ranges in this [`RangeSet`] in collation order.
+    pub fn iter(&self) -> slice::Iter<'_, (Bound<V>, Bound<V>)> {
+        self.ranges.iter()
+    }
+
+    /// Destructure this [`RangeSet`] into its disjoint ranges, in collation order.
+    pub fn into_ranges(self) -> Vec<(Bound<V>, Bound<V>)> {
+        self.ranges
+    }
+}
+
+impl<V: Clone, C: CollateRef<V>> RangeSet<V, C> {
+    /// Insert the given `range` into this [`RangeSet`],
+    /// coalescing it with any range which it overlaps or touches.
+    pub fn insert(&mut self, range: (Bound<V>, Bound<V>)) {
+        let collator = &self.collator;
+
+        if range_is_empty(collator, &range) {
+            return;
+        }
+
+        let start = self
+            .ranges
+            .partition_point(|r| !touches(collator, r.1.as_ref(), range.0.as_ref()));
+
+        let end = start
+            + self.ranges[start..]
+                .partition_point(|r| touches(collator, range.1.as_ref(), r.0.as_ref()));
+
+        let merged = self.ranges[start..end]
+            .iter()
+            .fold(range, |merged, r| hull(collator, &merged, r));
+
+        self.ranges.splice(start..end, [merged]);
+    }
+
+    /// Remove the values in the given `range` from this [`RangeSet`],
+    /// splitting any range which contains it.
+    pub fn remove(&mut self, range: &(Bound<V>, Bound<V>)) {
+        let collator = &self.collator;
+
+        if range_is_empty(collator, range) {
+            return;
+        }
+
+        let (start, end) = self.overlapping(range);
+
+        let remaining = self.ranges[start..end]
+            .iter()
+            .flat_map(|r| range_difference(collator, r, range))
+            .collect::<Vec<_>>();
+
+        self.ranges.splice(start..end, remaining);
+    }
+
+    /// Return `true` if any range in this [`RangeSet`] contains the given `value`.
+    pub fn contains_value(&self, value: &V) -> bool {
+        let collator = &self.collator;
+
+        let index = self
+            .ranges
+            .partition_point(|r| r.overlaps_value(value, collator) == Overlap::Less);
+
+        self.ranges
+            .get(index)
+            .map(|r| r.contains_value(value, collator))
+            .unwrap_or(false)
+    }
+
+    /// Return `true` if any range in this [`RangeSet`] has a value in common with `range`.
+    pub fn overlaps(&self, range: &(Bound<V>, Bound<V>)) -> bool {
+        if range_is_empty(&self.collator, range) {
+            false
+        } else {
+            let (start, end) = self.overlapping(range);
+            start < end
+        }
+    }
+
+    // return the indices of the ranges which have a value in common with the given `range`
+    fn overlapping(&self, range: &(Bound<V>, Bound<V>)) -> (usize, usize) {
+        let collator = &self.collator;
+
+        let start = self
+            .ranges
+            .partition_point(|r| !intersects(collator, r.1.as_ref(), range.0.as_ref()));
+
+        let end = start
+            + self.ranges[start..]
+                .partition_point(|r| intersects(collator, range.1.as_ref(), r.0.as_ref()));
+
+        (start, end)
+    }
+}
+
+impl<'a, V, C> IntoIterator for &'a RangeSet<V, C> {
+    type Item = &'a (Bound<V>, Bound<V>);
+    type IntoIter = slice::Iter<'a, (Bound<V>, Bound<V>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<V, C> IntoIterator for RangeSet<V, C> {
+    type Item = (Bound<V>, Bound<V>);
+    type IntoIter = std::vec::IntoIter<(Bound<V>, Bound<V>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_range_set() {
+        let mut set = RangeSet::new(Collator::<i32>::default());
+        set.insert((Bound::Included(1), Bound::Included(2)));
+        set.insert((Bound::Excluded(4), Bound::Included(6)));
+        set.insert((Bound::Excluded(2), Bound::Included(4)));
+        assert_eq!(set.len(), 1);
+
+        set.insert((Bound::Unbounded, Bound::Excluded(-5)));
+        set.remove(&(Bound::Included(-10), Bound::Excluded(3)));
+        set.remove(&(Bound::Excluded(6), Bound::Unbounded));
+
+        assert_eq!(
+            set.into_ranges(),
+            [
+                (Bound::Unbounded, Bound::Excluded(-10)),
+                (Bound::Included(3), Bound::Included(6)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// The placement of `Err` values relative to `Ok` values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ErrorOrder {
+    /// `Err` values collate before all `Ok` values
+    First,
+
+    /// `Err` values collate after all `Ok` values
+    #[default]
+    Last,
+}
+
+/// A collator for [`Result`]s, which places all `Err` values before or after all `Ok` values
+/// according to its [`ErrorOrder`], so that a partially-failed pipeline can still produce
+/// a deterministic collated output.
+///
+/// `Ok` values are collated using the value collator, and `Err` values using the error collator.
+/// Use [`ResultCollator::ignore_errors`] to collate all `Err` values equal.
+///
+/// Example:
+/// ```
+/// use collate::{Collate, Collator, ErrorOrder, ResultCollator};
+///
+/// let collator = ResultCollator::new(
+///     Collator::<u32>::default(),
+///     Collator::<String>::default(),
+///     ErrorOrder::Last,
+/// );
+///
+/// let mut values = vec![Err("b".to_string()), Ok(2), Err("a".to_string()), Ok(1)];
+/// values.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(values, [Ok(1), Ok(2), Err("a".to_string()), Err("b".to_string())]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ResultCollator<C, E> {
+    collator: C,
+    errors: E,
+    order: ErrorOrder,
+}
+
+impl<C, E> ResultCollator<C, E> {
+    /// Construct a new [`ResultCollator`] using the given value and error collators.
+    pub fn new(collator: C, errors: E, order: ErrorOrder) -> Self {
+        Self {
+            collator,
+            errors,
+            order,
+        }
+    }
+
+    /// Borrow the collator used to collate `Ok` values.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+
+    /// Borrow the collator used to collate `Err` values.
+    pub fn errors(&self) -> &E {
+        &self.errors
+    }
+
+    /// The [`ErrorOrder`] of this collator.
+    pub fn order(&self) -> ErrorOrder {
+        self.order
+    }
+}
+
+impl<C, T> ResultCollator<C, UnorderedCollator<T>> {
+    /// Construct a new [`ResultCollator`] which collates all `Err` values equal.
+    pub fn ignore_errors(collator: C, order: ErrorOrder) -> Self {
+        Self::new(collator, UnorderedCollator::default(), order)
+    }
+}
+
+impl<C: Collate, E: Collate> Collate for ResultCollator<C, E> {
+    type Value = Result<C::Value, E::Value>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        match (left, right) {
+            (Ok(l), Ok(r)) => self.collator.cmp(l, r),
+            (Err(l), Err(r)) => self.errors.cmp(l, r),
+            (Err(_), Ok(_)) => match self.order {
+                ErrorOrder::First => Ordering::Less,
+                ErrorOrder::Last => Ordering::Greater,
+            },
+            (Ok(_), Err(_)) => match self.order {
+                ErrorOrder::First => Ordering::Greater,
+                ErrorOrder::Last => Ordering::Less,
+            },
+        }
+    }
+}
+
+/// A collator which collates all values of type `T` equal, e.g. to ignore the `Err` values
+/// of a [`ResultCollator`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(bound = ""))]
+pub struct UnorderedCollator<T> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom: PhantomData<T>,
+}
+
+impl<T> Default for UnorderedCollator<T> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for UnorderedCollator<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for UnorderedCollator<T> {}
+
+impl<T> PartialEq for UnorderedCollator<T> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for UnorderedCollator<T> {}
+
+impl<T> fmt::Debug for UnorderedCollator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("UnorderedCollator")
+    }
+}
+
+impl<T> Collate for UnorderedCollator<T> {
+    type Value = T;
+
+    fn cmp(&self, _left: &Self::Value, _right: &Self::Value) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_result_collator() {
+        let collator = ResultCollator::ignore_errors(Collator::<u32>::default(), ErrorOrder::First);
+        assert_eq!(collator.cmp(&Err("x"), &Ok(0)), Ordering::Less);
+        assert_eq!(collator.cmp(&Err("x"), &Err("y")), Ordering::Equal);
+        assert_eq!(collator.cmp(&Ok(1), &Ok(0)), Ordering::Greater);
+
+        let errors = Collator::<&str>::default();
+        let collator = ResultCollator::new(Collator::<u32>::default(), errors, ErrorOrder::Last);
+        assert_eq!(collator.cmp(&Err("x"), &Ok(0)), Ordering::Greater);
+        assert_eq!(collator.cmp(&Err("x"), &Err("y")), Ordering::Less);
+    }
+}
@@ -0,0 +1,298 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef, Direction};
+
+/// The placement of null values relative to non-null values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NullOrder {
+    /// Null values collate before all non-null values
+    First,
+
+    /// Null values collate after all non-null values
+    Last,
+}
+
+impl NullOrder {
+    /// The default [`NullOrder`] for the given [`Direction`], treating null as greater than
+    /// any non-null value (i.e. `NULLS LAST` when ascending and `NULLS FIRST` when descending).
+    pub fn default_for(direction: Direction) -> Self {
+        match direction {
+            Direction::Ascending => Self::Last,
+            Direction::Descending => Self::First,
+        }
+    }
+
+    // collate two nullable values, using `cmp` to collate non-null values
+    pub(crate) fn cmp<T, F>(self, left: Option<&T>, right: Option<&T>, cmp: F) -> Ordering
+    where
+        F: FnOnce(&T, &T) -> Ordering,
+    {
+        match (left, right) {
+            (Some(l), Some(r)) => cmp(l, r),
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => match self {
+                Self::First => Ordering::Less,
+                Self::Last => Ordering::Greater,
+            },
+            (Some(_), None) => match self {
+                Self::First => Ordering::Greater,
+                Self::Last => Ordering::Less,
+            },
+        }
+    }
+}
+
+/// One key of a sort specification.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SortKey {
+    /// The index of the column to collate
+    pub column: usize,
+
+    /// The direction in which to collate the column
+    pub direction: Direction,
+
+    /// The placement of null values in the column
+    pub nulls: NullOrder,
+}
+
+impl SortKey {
+    /// Construct a new [`SortKey`] with the default [`NullOrder`] for the given `direction`.
+    pub fn new(column: usize, direction: Direction) -> Self {
+        Self {
+            column,
+            direction,
+            nulls: NullOrder::default_for(direction),
+        }
+    }
+}
+
+/// An error encountered while parsing a sort specification.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SortSpecError {
+    /// The sort specification, or one of its keys, is empty
+    Empty,
+
+    /// The schema does not contain a column with the given name
+    UnknownColumn(String),
+
+    /// The sort specification contains an unexpected token
+    Unexpected(String),
+}
+
+impl fmt::Display for SortSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty sort specification"),
+            Self::UnknownColumn(name) => write!(f, "unknown column: {name}"),
+            Self::Unexpected(token) => write!(f, "unexpected token in sort specification: {token}"),
+        }
+    }
+}
+
+impl std::error::Error for SortSpecError {}
+
+/// Parse a textual sort specification like `"name ASC NULLS LAST, age DESC"`,
+/// resolving column names to indices using the given `schema` callback.
+///
+/// Keywords are case-insensitive. If no direction is given, the default is `ASC`.
+/// If no null placement is given, the default is given by [`NullOrder::default_for`].
+///
+/// Example:
+/// ```
+/// use collate::{parse_sort_spec, Direction, NullOrder, SortKey};
+///
+/// let schema = |name: &str| ["name", "age"].iter().position(|column| *column == name);
+/// let keys = parse_sort_spec("name asc nulls first, age DESC", schema).expect("keys");
+///
+/// assert_eq!(
+///     keys,
+///     [
+///         SortKey { column: 0, direction: Direction::Ascending, nulls: NullOrder::First },
+///         SortKey { column: 1, direction: Direction::Descending, nulls: NullOrder::First },
+///     ]
+/// );
+/// ```
+pub fn parse_sort_spec<F>(spec: &str, schema: F) -> Result<Vec<SortKey>, SortSpecError>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    if spec.trim().is_empty() {
+        return Err(SortSpecError::Empty);
+    }
+
+    spec.split(',')
+        .map(|key| parse_sort_key(key, &schema))
+        .collect()
+}
+
+fn parse_sort_key<F>(key: &str, schema: F) -> Result<SortKey, SortSpecError>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut tokens = key.split_whitespace().peekable();
+
+    let name = tokens.next().ok_or(SortSpecError::Empty)?;
+    let column = schema(name).ok_or_else(|| SortSpecError::UnknownColumn(name.to_string()))?;
+
+    let direction = match tokens.peek() {
+        Some(token) if token.eq_ignore_ascii_case("asc") => {
+            tokens.next();
+            Direction::Ascending
+        }
+        Some(token) if token.eq_ignore_ascii_case("desc") => {
+            tokens.next();
+            Direction::Descending
+        }
+        _ => Direction::Ascending,
+    };
+
+    let nulls = match tokens.next() {
+        Some(token) if token.eq_ignore_ascii_case("nulls") => match tokens.next() {
+            Some(token) if token.eq_ignore_ascii_case("first") => NullOrder::First,
+            Some(token) if token.eq_ignore_ascii_case("last") => NullOrder::Last,
+            Some(token) => return Err(SortSpecError::Unexpected(token.to_string())),
+            None => return Err(SortSpecError::Unexpected(token.to_string())),
+        },
+        Some(token) => return Err(SortSpecError::Unexpected(token.to_string())),
+        None => NullOrder::default_for(direction),
+    };
+
+    if let Some(token) = tokens.next() {
+        return Err(SortSpecError::Unexpected(token.to_string()));
+    }
+
+    Ok(SortKey {
+        column,
+        direction,
+        nulls,
+    })
+}
+
+/// A collator for rows of nullable values, according to a list of [`SortKey`]s.
+///
+/// A column which is missing from a row is treated as null.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RowCollator<C> {
+    collator: C,
+    keys: Vec<SortKey>,
+}
+
+impl<C> RowCollator<C> {
+    /// Construct a new [`RowCollator`] which collates values using the given `collator`.
+    pub fn new(collator: C, keys: Vec<SortKey>) -> Self {
+        Self { collator, keys }
+    }
+
+    /// Construct a new [`RowCollator`] from a textual sort specification.
+    /// See [`parse_sort_spec`] for details.
+    pub fn parse<F>(collator: C, spec: &str, schema: F) -> Result<Self, SortSpecError>
+    where
+        F: Fn(&str) -> Option<usize>,
+    {
+        parse_sort_spec(spec, schema).map(|keys| Self::new(collator, keys))
+    }
+
+    /// Borrow the collator used to collate the values in each column.
+    pub fn inner(&self) -> &C {
+        &self.collator
+    }
+
+    /// Borrow the [`SortKey`]s of this collator.
+    pub fn keys(&self) -> &[SortKey] {
+        &self.keys
+    }
+}
+
+impl<C: Collate> Collate for RowCollator<C> {
+    type Value = Vec<Option<C::Value>>;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_slice(), right.as_slice())
+    }
+}
+
+impl<C: Collate> CollateRef<[Option<C::Value>]> for RowCollator<C> {
+    fn cmp_ref(&self, left: &[Option<C::Value>], right: &[Option<C::Value>]) -> Ordering {
+        for key in &self.keys {
+            let l_value = left.get(key.column).and_then(Option::as_ref);
+            let r_value = right.get(key.column).and_then(Option::as_ref);
+
+            let order = key.nulls.cmp(l_value, r_value, |l, r| {
+                key.direction.apply(self.collator.cmp(l, r))
+            });
+
+            if order != Ordering::Equal {
+                return order;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_parse_sort_spec() {
+        let schema = |name: &str| ["name", "age"].iter().position(|column| *column == name);
+
+        let keys = parse_sort_spec("age", schema).expect("keys");
+        assert_eq!(keys, [SortKey::new(1, Direction::Ascending)]);
+
+        assert_eq!(parse_sort_spec(" ", schema), Err(SortSpecError::Empty));
+        assert_eq!(parse_sort_spec("name,", schema), Err(SortSpecError::Empty));
+
+        assert_eq!(
+            parse_sort_spec("height", schema),
+            Err(SortSpecError::UnknownColumn("height".to_string()))
+        );
+
+        assert_eq!(
+            parse_sort_spec("name DESC NULLS", schema),
+            Err(SortSpecError::Unexpected("NULLS".to_string()))
+        );
+
+        assert_eq!(
+            parse_sort_spec("name ASC DESC", schema),
+            Err(SortSpecError::Unexpected("DESC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_row_collator() {
+        let schema = |name: &str| ["name", "age"].iter().position(|column| *column == name);
+        let collator = RowCollator::parse(Collator::<u32>::default(), "age DESC, name", schema)
+            .expect("collator");
+
+        let mut rows = vec![
+            vec![Some(1), Some(30)],
+            vec![None, Some(30)],
+            vec![Some(2), None],
+            vec![Some(3), Some(40)],
+            vec![Some(0), Some(30)],
+        ];
+
+        rows.sort_by(|l, r| collator.cmp(l, r));
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![Some(2), None],
+                vec![Some(3), Some(40)],
+                vec![Some(0), Some(30)],
+                vec![Some(1), Some(30)],
+                vec![None, Some(30)],
+            ]
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::cmp::Ordering;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// A collator for strings which ignores case and diacritics, for building lookup indices.
+///
+/// Strings are decomposed into canonical form (NFD), combining marks are removed,
+/// and the remaining characters are lower-cased before comparison by code point.
+/// This is not a locale-correct display order.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CollateRef, SearchCollator};
+///
+/// let collator = SearchCollator;
+/// assert_eq!(collator.cmp_ref("Éléonore", "ELEONORE"), Ordering::Equal);
+/// assert_eq!(collator.cmp_ref("eleonore", "Éléonore"), Ordering::Equal);
+/// assert_eq!(SearchCollator::search_key("Crème Brûlée"), "creme brulee");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchCollator;
+
+impl SearchCollator {
+    /// Return the search key of the given `value`, which collates equal to `value`.
+    pub fn search_key(value: &str) -> String {
+        fold(value).collect()
+    }
+}
+
+impl Collate for SearchCollator {
+    type Value = String;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ref(left.as_str(), right.as_str())
+    }
+}
+
+impl CollateRef<str> for SearchCollator {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        fold(left).cmp(fold(right))
+    }
+}
+
+fn fold(value: &str) -> impl Iterator<Item = char> + '_ {
+    value
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+}
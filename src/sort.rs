@@ -0,0 +1,455 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::CollateRef;
+
+/// Return the permutation of indices which sorts the given `slice` according to `collator`,
+/// without moving the elements of the `slice`. The sort is stable.
+///
+/// Example:
+/// ```
+/// use collate::{argsort_by_collator, Collator};
+///
+/// let column = ["b", "c", "a", "b"];
+/// let permutation = argsort_by_collator(&column, &Collator::default());
+/// assert_eq!(permutation, [2, 0, 3, 1]);
+/// ```
+pub fn argsort_by_collator<T, C>(slice: &[T], collator: &C) -> Vec<usize>
+where
+    C: CollateRef<T>,
+{
+    let mut permutation = (0..slice.len()).collect::<Vec<usize>>();
+    permutation.sort_by(|l, r| collator.cmp_ref(&slice[*l], &slice[*r]));
+    permutation
+}
+
+/// Reorder the given `slice` in place according to a `permutation` returned by
+/// [`argsort_by_collator`], so that the element at `permutation[i]` moves to index `i`.
+///
+/// This is useful to reorder several parallel columns according to one collated key column.
+///
+/// Panics: if `permutation` is not a permutation of the indices of `slice`.
+///
+/// Example:
+/// ```
+/// use collate::{apply_permutation, argsort_by_collator, Collator};
+///
+/// let mut names = ["carol", "alice", "bob"];
+/// let mut ages = [35, 30, 25];
+///
+/// let permutation = argsort_by_collator(&names, &Collator::default());
+/// apply_permutation(&mut names, &permutation);
+/// apply_permutation(&mut ages, &permutation);
+///
+/// assert_eq!(names, ["alice", "bob", "carol"]);
+/// assert_eq!(ages, [30, 25, 35]);
+/// ```
+pub fn apply_permutation<T>(slice: &mut [T], permutation: &[usize]) {
+    assert_eq!(slice.len(), permutation.len(), "permutation length");
+
+    let mut done = vec![false; slice.len()];
+
+    for start in 0..slice.len() {
+        if done[start] {
+            continue;
+        }
+
+        // follow the cycle which begins at `start`, swapping each element into place
+        let mut i = start;
+        loop {
+            done[i] = true;
+
+            let source = permutation[i];
+            assert!(source < slice.len(), "invalid permutation index {source}");
+
+            if source == start {
+                break;
+            }
+
+            assert!(
+                !done[source],
+                "invalid permutation: index {source} is repeated"
+            );
+            slice.swap(i, source);
+            i = source;
+        }
+    }
+}
+
+/// Return the permutation of indices which sorts the given `slice` according to `collator`,
+/// without moving the elements of the `slice`, using a parallel sort. The sort is stable.
+#[cfg(feature = "rayon")]
+pub fn par_argsort_by_collator<T, C>(slice: &[T], collator: &C) -> Vec<usize>
+where
+    T: Sync,
+    C: CollateRef<T> + Sync,
+{
+    use rayon::slice::ParallelSliceMut;
+
+    let mut permutation = (0..slice.len()).collect::<Vec<usize>>();
+    permutation.par_sort_by(|l, r| collator.cmp_ref(&slice[*l], &slice[*r]));
+    permutation
+}
+
+/// Return the rank of each element of the given `slice` according to `collator`,
+/// where equal elements share the same rank and leave a gap in the ranks which follow them,
+/// like the SQL `RANK()` function. Ranks start at 1.
+///
+/// Example:
+/// ```
+/// use collate::{rank_by_collator, Collator};
+///
+/// let scores = [30, 10, 20, 10];
+/// assert_eq!(rank_by_collator(&scores, &Collator::default()), [4, 1, 3, 1]);
+/// ```
+pub fn rank_by_collator<T, C>(slice: &[T], collator: &C) -> Vec<usize>
+where
+    C: CollateRef<T>,
+{
+    ranks_by_collator(slice, collator, RankMode::Competition)
+}
+
+/// Return the rank of each element of the given `slice` according to `collator`,
+/// where equal elements share the same rank without leaving a gap in the ranks which follow
+/// them, like the SQL `DENSE_RANK()` function. Ranks start at 1.
+///
+/// Example:
+/// ```
+/// use collate::{dense_rank_by_collator, Collator};
+///
+/// let scores = [30, 10, 20, 10];
+/// assert_eq!(dense_rank_by_collator(&scores, &Collator::default()), [3, 1, 2, 1]);
+/// ```
+pub fn dense_rank_by_collator<T, C>(slice: &[T], collator: &C) -> Vec<usize>
+where
+    C: CollateRef<T>,
+{
+    ranks_by_collator(slice, collator, RankMode::Dense)
+}
+
+/// How [`ranks_by_collator`] assigns ranks to elements which collate equal.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RankMode {
+    /// Equal elements share the same rank and leave a gap in the ranks which follow them,
+    /// like the SQL `RANK()` function
+    #[default]
+    Competition,
+
+    /// Equal elements share the same rank without leaving a gap,
+    /// like the SQL `DENSE_RANK()` function
+    Dense,
+
+    /// Every element has a distinct rank, and equal elements are ranked in their order
+    /// in the slice, like the SQL `ROW_NUMBER()` function
+    Ordinal,
+}
+
+/// Return the rank of each element of the given `slice` according to `collator`,
+/// assigning ranks to equal elements according to the given [`RankMode`]. Ranks start at 1.
+///
+/// Example:
+/// ```
+/// use collate::{ranks_by_collator, Collator, RankMode};
+///
+/// let scores = [30, 10, 20, 10];
+/// let collator = Collator::default();
+/// assert_eq!(ranks_by_collator(&scores, &collator, RankMode::Competition), [4, 1, 3, 1]);
+/// assert_eq!(ranks_by_collator(&scores, &collator, RankMode::Dense), [3, 1, 2, 1]);
+/// assert_eq!(ranks_by_collator(&scores, &collator, RankMode::Ordinal), [4, 1, 3, 2]);
+/// ```
+pub fn ranks_by_collator<T, C>(slice: &[T], collator: &C, mode: RankMode) -> Vec<usize>
+where
+    C: CollateRef<T>,
+{
+    let permutation = argsort_by_collator(slice, collator);
+    let mut ranks = vec![0; slice.len()];
+    let mut rank = 0;
+
+    for (position, i) in permutation.iter().copied().enumerate() {
+        let tie = mode != RankMode::Ordinal && position > 0 && {
+            let previous = permutation[position - 1];
+            collator.cmp_ref(&slice[previous], &slice[i]) == Ordering::Equal
+        };
+
+        if !tie {
+            rank = match mode {
+                RankMode::Dense => rank + 1,
+                RankMode::Competition | RankMode::Ordinal => position + 1,
+            };
+        }
+
+        ranks[i] = rank;
+    }
+
+    ranks
+}
+
+/// Split the given collated `slice` into runs of consecutive elements which collate equal,
+/// e.g. to compute an aggregate of each group in a `GROUP BY` query over sorted data.
+///
+/// If the `slice` is not collated, equal elements which are not adjacent form separate groups.
+///
+/// Example:
+/// ```
+/// use collate::{group_equal_by_collator, Collator};
+///
+/// let column = [1, 1, 2, 3, 3, 3];
+/// let groups = group_equal_by_collator(&column, &Collator::default());
+/// assert_eq!(groups, [&[1, 1][..], &[2], &[3, 3, 3]]);
+/// ```
+pub fn group_equal_by_collator<'a, T, C>(slice: &'a [T], collator: &C) -> Vec<&'a [T]>
+where
+    C: CollateRef<T>,
+{
+    slice
+        .chunk_by(|l, r| collator.cmp_ref(l, r) == Ordering::Equal)
+        .collect()
+}
+
+/// Reorder the given `slice` such that the element at `index` is in its collated position,
+/// like [`slice::select_nth_unstable_by`] but using `collator`, and return the elements before
+/// that position, the element at that position, and the elements after it.
+///
+/// Panics: if `index` is out of bounds.
+///
+/// Example:
+/// ```
+/// use collate::{select_nth_by_collator, FloatCollator};
+///
+/// let collator = FloatCollator::<f64>::default();
+/// let mut samples = [2.5, -1.0, 7.0, 0.5, 3.0];
+/// let (_, median, _) = select_nth_by_collator(&mut samples, 2, &collator);
+/// assert_eq!(*median, 2.5);
+/// ```
+pub fn select_nth_by_collator<'a, T, C>(
+    slice: &'a mut [T],
+    index: usize,
+    collator: &C,
+) -> (&'a mut [T], &'a mut T, &'a mut [T])
+where
+    C: CollateRef<T>,
+{
+    slice.select_nth_unstable_by(index, |l, r| collator.cmp_ref(l, r))
+}
+
+/// Compute the exact quantiles of the given `slice` according to `collator`,
+/// returning the element at each of the given `fractions` (e.g. `0.5` for the median)
+/// in the same order as `fractions`. The `slice` is reordered in the process.
+///
+/// Like the `quantiles` function for streams, this uses the nearest-rank method,
+/// i.e. the quantile at fraction `p` is the element at 1-based position `ceil(p * len)`
+/// (or the first element, if `p` is zero).
+///
+/// Returns `None` if the `slice` is empty and any quantile is requested.
+///
+/// Panics: if any of the given `fractions` is not in the range `0.0..=1.0`.
+///
+/// Example:
+/// ```
+/// use collate::{quantiles_by_collator, FloatCollator};
+///
+/// let collator = FloatCollator::<f64>::default();
+/// let mut samples = [9.0, 1.0, 8.0, 2.0, 7.0, 3.0, 6.0, 4.0, 5.0, 10.0];
+/// let quantiles = quantiles_by_collator(&mut samples, &[0.5, 0.9, 0.0], &collator);
+/// assert_eq!(quantiles, Some(vec![&5.0, &9.0, &1.0]));
+/// ```
+pub fn quantiles_by_collator<'a, T, C>(
+    slice: &'a mut [T],
+    fractions: &[f64],
+    collator: &C,
+) -> Option<Vec<&'a T>>
+where
+    C: CollateRef<T>,
+{
+    if slice.is_empty() {
+        return if fractions.is_empty() {
+            Some(vec![])
+        } else {
+            None
+        };
+    }
+
+    let indices = fractions
+        .iter()
+        .map(|fraction| quantile_index(slice.len(), *fraction))
+        .collect::<Vec<usize>>();
+
+    let mut selected = indices.clone();
+    selected.sort();
+    selected.dedup();
+
+    // each selection partitions the slice, so the next one only needs to search the remainder
+    let mut start = 0;
+    for index in selected {
+        select_nth_by_collator(&mut slice[start..], index - start, collator);
+        start = index;
+    }
+
+    let slice = &*slice;
+    Some(indices.into_iter().map(|index| &slice[index]).collect())
+}
+
+// return the 0-based index of the quantile at `fraction` of `len` items, by nearest rank
+pub(crate) fn quantile_index(len: usize, fraction: f64) -> usize {
+    assert!(
+        (0.0..=1.0).contains(&fraction),
+        "invalid quantile: {fraction}"
+    );
+
+    let rank = (fraction * len as f64).ceil() as usize;
+    rank.clamp(1, len) - 1
+}
+
+/// Return `true` if the given `slice` is sorted according to `collator`,
+/// e.g. to check that an input to `merge` or `diff` is collated.
+///
+/// Example:
+/// ```
+/// use collate::{is_sorted_by_collator, is_strictly_sorted_by_collator, Collator};
+///
+/// let collator = Collator::<u32>::default();
+/// assert!(is_sorted_by_collator(&[1, 2, 2, 3], &collator));
+/// assert!(!is_strictly_sorted_by_collator(&[1, 2, 2, 3], &collator));
+/// assert!(!is_sorted_by_collator(&[1, 3, 2], &collator));
+/// ```
+pub fn is_sorted_by_collator<T, C>(slice: &[T], collator: &C) -> bool
+where
+    C: CollateRef<T>,
+{
+    first_unsorted(slice.iter(), collator, false).is_none()
+}
+
+/// Return `true` if the given `slice` is sorted according to `collator`
+/// and contains no two elements which collate equal.
+pub fn is_strictly_sorted_by_collator<T, C>(slice: &[T], collator: &C) -> bool
+where
+    C: CollateRef<T>,
+{
+    first_unsorted(slice.iter(), collator, true).is_none()
+}
+
+/// Return `true` if the items of the given `iter` are sorted according to `collator`.
+///
+/// Example:
+/// ```
+/// use collate::{is_iter_sorted_by_collator, Collator};
+///
+/// let collator = Collator::<String>::default();
+/// let words = "apple banana cherry".split(' ');
+/// assert!(is_iter_sorted_by_collator::<_, str, _>(words, &collator));
+/// ```
+pub fn is_iter_sorted_by_collator<I, T, C>(iter: I, collator: &C) -> bool
+where
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    first_unsorted(iter, collator, false).is_none()
+}
+
+/// Return `true` if the items of the given `iter` are sorted according to `collator`
+/// and no two items collate equal.
+pub fn is_iter_strictly_sorted_by_collator<I, T, C>(iter: I, collator: &C) -> bool
+where
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    first_unsorted(iter, collator, true).is_none()
+}
+
+/// Assert that the given `slice` is sorted according to `collator`, in debug builds only.
+///
+/// Panics: in a debug build, if the `slice` is not sorted,
+/// with the index of the first element which collates before its predecessor.
+#[track_caller]
+pub fn debug_assert_sorted_by_collator<T, C>(slice: &[T], collator: &C)
+where
+    C: CollateRef<T>,
+{
+    if cfg!(debug_assertions) {
+        if let Some(index) = first_unsorted(slice.iter(), collator, false) {
+            panic!("slice is not collated: element {index} collates before its predecessor");
+        }
+    }
+}
+
+/// Assert that the given `slice` is collated according to `collator`.
+///
+/// Unlike [`debug_assert_sorted_by_collator`], this also checks the `slice` in a release build.
+///
+/// Panics: if the `slice` is not collated, with the first pair of adjacent elements
+/// which are out of order.
+///
+/// Example:
+/// ```should_panic
+/// use collate::{assert_collated, Collator};
+///
+/// // panics with "slice is not collated: 3 at index 1 collates after 2 at index 2"
+/// assert_collated(&Collator::default(), &[1, 3, 2]);
+/// ```
+#[track_caller]
+pub fn assert_collated<T, C>(collator: &C, slice: &[T])
+where
+    T: fmt::Debug,
+    C: CollateRef<T>,
+{
+    if let Some(index) = first_unsorted(slice.iter(), collator, false) {
+        let (previous, next) = (&slice[index - 1], &slice[index]);
+        let position = index - 1;
+
+        panic!(
+            "slice is not collated: \
+            {previous:?} at index {position} collates after {next:?} at index {index}"
+        );
+    }
+}
+
+// return the index of the first item which collates before (or, if `strict`, equal to)
+// the item before it
+fn first_unsorted<I, T, C>(iter: I, collator: &C, strict: bool) -> Option<usize>
+where
+    I: IntoIterator,
+    I::Item: Borrow<T>,
+    T: ?Sized,
+    C: CollateRef<T>,
+{
+    let mut iter = iter.into_iter();
+    let mut previous = iter.next()?;
+
+    for (index, item) in iter.enumerate() {
+        let order = collator.cmp_ref(previous.borrow(), item.borrow());
+
+        if order == Ordering::Greater || (strict && order == Ordering::Equal) {
+            return Some(index + 1);
+        }
+
+        previous = item;
+    }
+
+    None
+}
+
+#[cfg(all(test, feature = "rayon"))]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    #[test]
+    fn test_par_argsort() {
+        let column = (0..1000u32).map(|i| (i * 7919) % 100).collect::<Vec<u32>>();
+        let collator = Collator::default();
+
+        assert_eq!(
+            par_argsort_by_collator(&column, &collator),
+            argsort_by_collator(&column, &collator)
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::async_merge::{compare, Comparison};
+use super::Fuse;
+use crate::AsyncCollate;
+
+/// The stream type returned by [`async_diff`].
+#[pin_project]
+pub struct AsyncDiff<C, T, L, R> {
+    collator: Option<C>,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<T>,
+    pending_right: Option<T>,
+    comparison: Option<Comparison<C, T>>,
+}
+
+impl<C, T, L, R> Stream for AsyncDiff<C, T, L, R>
+where
+    C: AsyncCollate<Value = T> + Send + 'static,
+    T: Send + 'static,
+    L: Stream<Item = T> + Unpin,
+    R: Stream<Item = T> + Unpin,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if let Some(comparison) = this.comparison {
+                let (collator, l_value, r_value, order) = ready!(comparison.as_mut().poll(cxt));
+                *this.comparison = None;
+                *this.collator = Some(collator);
+
+                match order {
+                    Ordering::Equal => {
+                        // this value is present in the right stream, so drop it
+                        continue;
+                    }
+                    Ordering::Less => {
+                        // this value is not present in the right stream, so return it
+                        *this.pending_right = Some(r_value);
+                        break Some(l_value);
+                    }
+                    Ordering::Greater => {
+                        // this value could be present in the right stream--wait and see
+                        *this.pending_left = Some(l_value);
+                    }
+                }
+            }
+
+            let left_done = if this.left.is_done() {
+                true
+            } else if this.pending_left.is_none() {
+                match ready!(this.left.as_mut().poll_next(cxt)) {
+                    Some(value) => {
+                        *this.pending_left = Some(value);
+                        false
+                    }
+                    None => true,
+                }
+            } else {
+                false
+            };
+
+            let right_done = if this.right.is_done() {
+                true
+            } else if this.pending_right.is_none() {
+                match ready!(this.right.as_mut().poll_next(cxt)) {
+                    Some(value) => {
+                        *this.pending_right = Some(value);
+                        false
+                    }
+                    None => true,
+                }
+            } else {
+                false
+            };
+
+            if this.pending_left.is_some() && this.pending_right.is_some() {
+                let l_value = this.pending_left.take().expect("left value");
+                let r_value = this.pending_right.take().expect("right value");
+                let collator = this.collator.take().expect("collator");
+                *this.comparison = Some(compare(collator, l_value, r_value));
+            } else if right_done && this.pending_left.is_some() {
+                break this.pending_left.take();
+            } else if left_done {
+                break None;
+            }
+        })
+    }
+}
+
+/// Compute the difference of two collated [`Stream`]s using the given asynchronous `collator`,
+/// i.e. return the items in `left` that are not in `right`, awaiting each comparison.
+/// Both input streams **must** be collated.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn async_diff<C, T, L, R>(collator: C, left: L, right: R) -> AsyncDiff<C, T, L, R>
+where
+    C: AsyncCollate<Value = T> + Send + 'static,
+    T: Send + 'static,
+    L: Stream<Item = T>,
+    R: Stream<Item = T>,
+{
+    AsyncDiff {
+        collator: Some(collator),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+        comparison: None,
+    }
+}
@@ -0,0 +1,128 @@
+use std::cmp::Ordering;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::AsyncCollate;
+
+/// A pending comparison, which returns ownership of the collator and both values when done.
+pub(super) type Comparison<C, T> = Pin<Box<dyn Future<Output = (C, T, T, Ordering)> + Send>>;
+
+pub(super) fn compare<C, T>(collator: C, left: T, right: T) -> Comparison<C, T>
+where
+    C: AsyncCollate<Value = T> + Send + 'static,
+    T: Send + 'static,
+{
+    Box::pin(async move {
+        let order = collator.cmp(&left, &right).await;
+        (collator, left, right, order)
+    })
+}
+
+/// The stream type returned by [`async_merge`].
+#[pin_project]
+pub struct AsyncMerge<C, T, L, R> {
+    collator: Option<C>,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<T>,
+    pending_right: Option<T>,
+    comparison: Option<Comparison<C, T>>,
+}
+
+impl<C, T, L, R> Stream for AsyncMerge<C, T, L, R>
+where
+    C: AsyncCollate<Value = T> + Send + 'static,
+    T: Send + 'static,
+    L: Stream<Item = T> + Unpin,
+    R: Stream<Item = T> + Unpin,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        loop {
+            if let Some(comparison) = this.comparison {
+                let (collator, l_value, r_value, order) = ready!(comparison.as_mut().poll(cxt));
+                *this.comparison = None;
+                *this.collator = Some(collator);
+
+                let value = match order {
+                    Ordering::Equal => l_value,
+                    Ordering::Less => {
+                        *this.pending_right = Some(r_value);
+                        l_value
+                    }
+                    Ordering::Greater => {
+                        *this.pending_left = Some(l_value);
+                        r_value
+                    }
+                };
+
+                return Poll::Ready(Some(value));
+            }
+
+            if !this.left.is_done() && this.pending_left.is_none() {
+                if let Some(value) = ready!(this.left.as_mut().poll_next(cxt)) {
+                    *this.pending_left = Some(value);
+                }
+            }
+
+            if !this.right.is_done() && this.pending_right.is_none() {
+                if let Some(value) = ready!(this.right.as_mut().poll_next(cxt)) {
+                    *this.pending_right = Some(value);
+                }
+            }
+
+            let value = match (this.pending_left.take(), this.pending_right.take()) {
+                (Some(l_value), Some(r_value)) => {
+                    let collator = this.collator.take().expect("collator");
+                    *this.comparison = Some(compare(collator, l_value, r_value));
+                    continue;
+                }
+                (Some(l_value), None) if this.right.is_done() => Some(l_value),
+                (None, Some(r_value)) if this.left.is_done() => Some(r_value),
+                (None, None) if this.left.is_done() && this.right.is_done() => None,
+                (l_value, r_value) => {
+                    *this.pending_left = l_value;
+                    *this.pending_right = r_value;
+
+                    // an input stream neither yielded an item nor finished, so try again later
+                    cxt.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+            };
+
+            return Poll::Ready(value);
+        }
+    }
+}
+
+/// Merge two collated [`Stream`]s into one using the given asynchronous `collator`,
+/// awaiting each comparison. Both input streams **must** be collated.
+/// If either input stream is not collated, the order of the output stream is undefined.
+pub fn async_merge<C, T, L, R>(collator: C, left: L, right: R) -> AsyncMerge<C, T, L, R>
+where
+    C: AsyncCollate<Value = T> + Send + 'static,
+    T: Send + 'static,
+    L: Stream<Item = T>,
+    R: Stream<Item = T>,
+{
+    AsyncMerge {
+        collator: Some(collator),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+        comparison: None,
+    }
+}
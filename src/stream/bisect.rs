@@ -0,0 +1,55 @@
+use std::cmp::Ordering;
+use std::future::Future;
+use std::ops::Bound;
+
+use crate::CollateEquivalent;
+
+/// Bisect a collated random-access `source` of `len` items, where `get(index)` asynchronously
+/// loads the item at `index`, for example from an on-disk B-Tree node.
+///
+/// Returns the index of the first item which does not lie before the given `bound`, i.e.
+/// the first item not less than an `Included` bound, the first item greater than an
+/// `Excluded` bound, or zero if `Unbounded`. This is the index at which to insert a new item
+/// equal to the bound value, or the start index of a range beginning with the given `bound`.
+///
+/// The `source` **must** be collated. If not, the returned index is undefined.
+pub async fn try_bisect<C, T, Q, E, F, Fut>(
+    collator: &C,
+    len: usize,
+    mut get: F,
+    bound: Bound<&Q>,
+) -> Result<usize, E>
+where
+    C: CollateEquivalent<T, Q>,
+    Q: ?Sized,
+    F: FnMut(usize) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let (value, strict) = match bound {
+        Bound::Unbounded => return Ok(0),
+        Bound::Included(value) => (value, false),
+        Bound::Excluded(value) => (value, true),
+    };
+
+    let mut lo = 0;
+    let mut hi = len;
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let item = get(mid).await?;
+
+        let before = match collator.cmp_equivalent(&item, value) {
+            Ordering::Less => true,
+            Ordering::Equal => strict,
+            Ordering::Greater => false,
+        };
+
+        if before {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok(lo)
+}
@@ -2,9 +2,10 @@ use std::cmp::Ordering;
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use futures::stream::{Fuse, Stream, StreamExt};
+use futures_core::Stream;
 use pin_project::pin_project;
 
+use super::Fuse;
 use crate::CollateRef;
 
 /// The stream type returned by [`diff`].
@@ -103,8 +104,8 @@ where
 {
     Diff {
         collator,
-        left: left.fuse(),
-        right: right.fuse(),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
         pending_left: None,
         pending_right: None,
     }
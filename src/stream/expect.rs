@@ -0,0 +1,64 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use crate::CollateRef;
+
+/// The stream type returned by [`expect_collated`].
+#[pin_project]
+pub struct ExpectCollated<C, T, S> {
+    collator: C,
+
+    #[pin]
+    source: S,
+
+    previous: Option<T>,
+}
+
+impl<C, T, S> Stream for ExpectCollated<C, T, S>
+where
+    C: CollateRef<T>,
+    T: Clone + fmt::Debug,
+    S: Stream<Item = T>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        let next = ready!(this.source.poll_next(cxt));
+
+        if cfg!(debug_assertions) {
+            if let Some(next) = &next {
+                if let Some(previous) = this.previous.replace(next.clone()) {
+                    if this.collator.cmp_ref(&previous, next) == Ordering::Greater {
+                        panic!("stream is not collated: {previous:?} collates after {next:?}");
+                    }
+                }
+            }
+        }
+
+        Poll::Ready(next)
+    }
+}
+
+/// Pass through the items of the given `source` [`Stream`], checking in a debug build
+/// that they are collated according to `collator`, e.g. to validate the input of `merge` or `diff`.
+///
+/// Panics: in a debug build, if an item collates before the item which precedes it.
+pub fn expect_collated<C, T, S>(collator: C, source: S) -> ExpectCollated<C, T, S>
+where
+    C: CollateRef<T>,
+    T: Clone + fmt::Debug,
+    S: Stream<Item = T>,
+{
+    ExpectCollated {
+        collator,
+        source,
+        previous: None,
+    }
+}
@@ -0,0 +1,62 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::{Stream, TryStream};
+use pin_project::pin_project;
+
+/// A stream which yields `None` forever after its source stream has ended,
+/// so that the combinators in this module can check whether an input is done.
+#[pin_project]
+pub(crate) struct Fuse<S> {
+    #[pin]
+    source: S,
+    done: bool,
+}
+
+impl<S> Fuse<S> {
+    pub(crate) fn new(source: S) -> Self {
+        Self {
+            source,
+            done: false,
+        }
+    }
+
+    /// Return `true` if the source stream has ended.
+    pub(crate) fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl<S: TryStream> Fuse<S> {
+    /// Poll the source [`TryStream`] for its next item.
+    pub(crate) fn try_poll_next(
+        self: Pin<&mut Self>,
+        cxt: &mut Context,
+    ) -> Poll<Option<Result<S::Ok, S::Error>>> {
+        let this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let item = ready!(this.source.try_poll_next(cxt));
+        *this.done = item.is_none();
+        Poll::Ready(item)
+    }
+}
+
+impl<S: Stream> Stream for Fuse<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let item = ready!(this.source.poll_next(cxt));
+        *this.done = item.is_none();
+        Poll::Ready(item)
+    }
+}
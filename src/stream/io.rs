@@ -0,0 +1,176 @@
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use futures_io::AsyncRead;
+use pin_project::pin_project;
+
+use super::{try_merge_many, TryMergeMany};
+use crate::CollateRef;
+
+const CHUNK_SIZE: usize = 8192;
+
+/// A decoder of records from a byte buffer, for use with [`decode`].
+pub trait Decoder {
+    /// The type of record to decode
+    type Item;
+
+    /// The type of error which can occur while decoding
+    type Error: From<io::Error>;
+
+    /// Decode and remove the next record from the front of the given `buffer`,
+    /// or return `None` if the buffer does not yet contain a complete record.
+    fn decode(&mut self, buffer: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Decode and remove the next record from the front of the given `buffer`
+    /// after the end of the input has been reached.
+    ///
+    /// By default, this returns an [`io::ErrorKind::UnexpectedEof`] error
+    /// if the `buffer` contains an incomplete record.
+    fn decode_eof(&mut self, buffer: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(buffer)? {
+            Some(item) => Ok(Some(item)),
+            None if buffer.is_empty() => Ok(None),
+            None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "incomplete record").into()),
+        }
+    }
+}
+
+/// A [`Decoder`] of newline-delimited UTF-8 strings. A trailing `'\r'` is removed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LineDecoder;
+
+impl Decoder for LineDecoder {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, buffer: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        let end = match buffer.iter().position(|byte| *byte == b'\n') {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+
+        let mut line = buffer.drain(..=end).collect::<Vec<u8>>();
+        line.pop();
+
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        String::from_utf8(line)
+            .map(Some)
+            .map_err(|cause| io::Error::new(io::ErrorKind::InvalidData, cause))
+    }
+
+    fn decode_eof(&mut self, buffer: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        if buffer.is_empty() {
+            Ok(None)
+        } else if let Some(line) = self.decode(buffer)? {
+            Ok(Some(line))
+        } else {
+            // the last line does not end with a newline
+            buffer.push(b'\n');
+            self.decode(buffer)
+        }
+    }
+}
+
+/// The stream type returned by [`decode`].
+#[pin_project]
+pub struct Decode<R, D> {
+    #[pin]
+    reader: R,
+    decoder: D,
+    buffer: Vec<u8>,
+    chunk: Box<[u8]>,
+    eof: bool,
+    done: bool,
+}
+
+impl<R, D> Stream for Decode<R, D>
+where
+    R: AsyncRead,
+    D: Decoder,
+{
+    type Item = Result<D::Item, D::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if *this.done {
+                break None;
+            }
+
+            let decoded = if *this.eof {
+                this.decoder.decode_eof(this.buffer)
+            } else {
+                this.decoder.decode(this.buffer)
+            };
+
+            match decoded {
+                Ok(Some(item)) => break Some(Ok(item)),
+                Ok(None) if *this.eof => {
+                    *this.done = true;
+                    break None;
+                }
+                Ok(None) => {}
+                Err(cause) => {
+                    *this.done = true;
+                    break Some(Err(cause));
+                }
+            }
+
+            match ready!(this.reader.as_mut().poll_read(cxt, this.chunk)) {
+                Ok(0) => *this.eof = true,
+                Ok(len) => this.buffer.extend_from_slice(&this.chunk[..len]),
+                Err(cause) if cause.kind() == io::ErrorKind::Interrupted => {}
+                Err(cause) => {
+                    *this.done = true;
+                    break Some(Err(cause.into()));
+                }
+            }
+        })
+    }
+}
+
+/// Decode a [`Stream`] of records from the given `reader` using the given `decoder`.
+pub fn decode<R, D>(reader: R, decoder: D) -> Decode<R, D>
+where
+    R: AsyncRead,
+    D: Decoder,
+{
+    Decode {
+        reader,
+        decoder,
+        buffer: Vec::with_capacity(CHUNK_SIZE),
+        chunk: vec![0; CHUNK_SIZE].into_boxed_slice(),
+        eof: false,
+        done: false,
+    }
+}
+
+/// Merge the collated records decoded from each of the given `readers` into a single
+/// collated stream using the given `collator`, for example to compact sorted files.
+/// The records of each reader **must** be collated.
+///
+/// See [`merge_many`](crate::merge_many) for details.
+pub fn merge_readers<C, R, D, I>(
+    collator: C,
+    decoder: D,
+    readers: I,
+) -> TryMergeMany<C, D::Item, Decode<R, D>>
+where
+    C: CollateRef<D::Item>,
+    R: AsyncRead,
+    D: Decoder + Clone,
+    D::Error: std::error::Error,
+    I: IntoIterator<Item = R>,
+{
+    let sources = readers
+        .into_iter()
+        .map(|reader| decode(reader, decoder.clone()));
+
+    try_merge_many(collator, sources)
+}
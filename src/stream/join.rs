@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`anti_join`] and [`semi_join`].
+#[pin_project]
+pub struct Join<C, K, V, L, R> {
+    collator: C,
+    keep_present: bool,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<(K, V)>,
+    pending_right: Option<K>,
+}
+
+impl<C, K, V, L, R> Stream for Join<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    L: Stream<Item = (K, V)> + Unpin,
+    R: Stream<Item = K> + Unpin,
+{
+    type Item = (K, V);
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if !this.left.is_done() && this.pending_left.is_none() {
+                if let Some(entry) = ready!(this.left.as_mut().poll_next(cxt)) {
+                    *this.pending_left = Some(entry);
+                }
+            }
+
+            if !this.right.is_done() && this.pending_right.is_none() {
+                if let Some(key) = ready!(this.right.as_mut().poll_next(cxt)) {
+                    *this.pending_right = Some(key);
+                }
+            }
+
+            match (this.pending_left.as_ref(), this.pending_right.as_ref()) {
+                (Some((l_key, _)), Some(r_key)) => match this.collator.cmp_ref(l_key, r_key) {
+                    Ordering::Equal => {
+                        // this key is present in the right stream--keep it pending in case
+                        // the left stream has another entry with the same key
+                        let entry = this.pending_left.take();
+
+                        if *this.keep_present {
+                            break entry;
+                        }
+                    }
+                    Ordering::Less => {
+                        // this key is not present in the right stream
+                        let entry = this.pending_left.take();
+
+                        if !*this.keep_present {
+                            break entry;
+                        }
+                    }
+                    Ordering::Greater => {
+                        // this key could be present in the right stream--wait and see
+                        this.pending_right.take();
+                    }
+                },
+                (Some(_), None) if *this.keep_present => break None,
+                (Some(_), None) => break this.pending_left.take(),
+                (None, _) => break None,
+            }
+        })
+    }
+}
+
+/// Filter a collated [`Stream`] of `(key, value)` entries to only those whose key is **not**
+/// present in the collated `keys` stream, using the given `collator` for keys.
+/// Both input streams **must** be collated.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn anti_join<C, K, V, L, R>(collator: C, entries: L, keys: R) -> Join<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    L: Stream<Item = (K, V)>,
+    R: Stream<Item = K>,
+{
+    Join {
+        collator,
+        keep_present: false,
+        left: Fuse::new(entries),
+        right: Fuse::new(keys),
+        pending_left: None,
+        pending_right: None,
+    }
+}
+
+/// Filter a collated [`Stream`] of `(key, value)` entries to only those whose key is present
+/// in the collated `keys` stream, using the given `collator` for keys.
+/// Both input streams **must** be collated.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn semi_join<C, K, V, L, R>(collator: C, entries: L, keys: R) -> Join<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    L: Stream<Item = (K, V)>,
+    R: Stream<Item = K>,
+{
+    Join {
+        collator,
+        keep_present: true,
+        left: Fuse::new(entries),
+        right: Fuse::new(keys),
+        pending_left: None,
+        pending_right: None,
+    }
+}
@@ -2,9 +2,10 @@ use std::cmp::Ordering;
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use futures::stream::{Fuse, Stream, StreamExt};
+use futures_core::Stream;
 use pin_project::pin_project;
 
+use super::Fuse;
 use crate::CollateRef;
 
 /// The stream type returned by [`merge`].
@@ -32,56 +33,37 @@ where
     type Item = T;
 
     fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
-        let this = self.project();
+        let mut this = self.project();
 
-        let left_done = if this.left.is_done() {
-            true
-        } else if this.pending_left.is_none() {
-            match ready!(this.left.poll_next(cxt)) {
-                Some(value) => {
-                    *this.pending_left = Some(value);
-                    false
-                }
-                None => true,
+        if !this.left.is_done() && this.pending_left.is_none() {
+            if let Some(value) = ready!(this.left.as_mut().poll_next(cxt)) {
+                *this.pending_left = Some(value);
             }
-        } else {
-            false
-        };
+        }
 
-        let right_done = if this.right.is_done() {
-            true
-        } else if this.pending_right.is_none() {
-            match ready!(this.right.poll_next(cxt)) {
-                Some(value) => {
-                    *this.pending_right = Some(value);
-                    false
-                }
-                None => true,
+        if !this.right.is_done() && this.pending_right.is_none() {
+            if let Some(value) = ready!(this.right.as_mut().poll_next(cxt)) {
+                *this.pending_right = Some(value);
             }
-        } else {
-            false
-        };
-
-        let value = if this.pending_left.is_some() && this.pending_right.is_some() {
-            let l_value = this.pending_left.as_ref().unwrap();
-            let r_value = this.pending_right.as_ref().unwrap();
+        }
 
-            match this.collator.cmp_ref(l_value, r_value) {
+        let value = match (this.pending_left.as_ref(), this.pending_right.as_ref()) {
+            (Some(l_value), Some(r_value)) => match this.collator.cmp_ref(l_value, r_value) {
                 Ordering::Equal => {
                     this.pending_right.take();
                     this.pending_left.take()
                 }
                 Ordering::Less => this.pending_left.take(),
                 Ordering::Greater => this.pending_right.take(),
+            },
+            (Some(_), None) if this.right.is_done() => this.pending_left.take(),
+            (None, Some(_)) if this.left.is_done() => this.pending_right.take(),
+            (None, None) if this.left.is_done() && this.right.is_done() => None,
+            _ => {
+                // an input stream neither yielded an item nor finished, so try again later
+                cxt.waker().wake_by_ref();
+                return Poll::Pending;
             }
-        } else if right_done && this.pending_left.is_some() {
-            this.pending_left.take()
-        } else if left_done && this.pending_right.is_some() {
-            this.pending_right.take()
-        } else if left_done && right_done {
-            None
-        } else {
-            unreachable!("both streams to merge are still pending")
         };
 
         Poll::Ready(value)
@@ -99,8 +81,8 @@ where
 {
     Merge {
         collator,
-        left: left.fuse(),
-        right: right.fuse(),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
         pending_left: None,
         pending_right: None,
     }
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`merge_kv`].
+#[pin_project]
+pub struct MergeKV<C, F, K, V, L, R> {
+    collator: C,
+    combine: F,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<(K, V)>,
+    pending_right: Option<(K, V)>,
+}
+
+impl<C, F, K, V, L, R> Stream for MergeKV<C, F, K, V, L, R>
+where
+    C: CollateRef<K>,
+    F: FnMut(V, V) -> V,
+    L: Stream<Item = (K, V)> + Unpin,
+    R: Stream<Item = (K, V)> + Unpin,
+{
+    type Item = (K, V);
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if !this.left.is_done() && this.pending_left.is_none() {
+            if let Some(entry) = ready!(this.left.as_mut().poll_next(cxt)) {
+                *this.pending_left = Some(entry);
+            }
+        }
+
+        if !this.right.is_done() && this.pending_right.is_none() {
+            if let Some(entry) = ready!(this.right.as_mut().poll_next(cxt)) {
+                *this.pending_right = Some(entry);
+            }
+        }
+
+        let entry = match (this.pending_left.as_ref(), this.pending_right.as_ref()) {
+            (Some((l_key, _)), Some((r_key, _))) => match this.collator.cmp_ref(l_key, r_key) {
+                Ordering::Equal => {
+                    let (key, l_value) = this.pending_left.take().expect("left entry");
+                    let (_, r_value) = this.pending_right.take().expect("right entry");
+                    Some((key, (this.combine)(l_value, r_value)))
+                }
+                Ordering::Less => this.pending_left.take(),
+                Ordering::Greater => this.pending_right.take(),
+            },
+            (Some(_), None) => this.pending_left.take(),
+            (None, Some(_)) => this.pending_right.take(),
+            (None, None) => None,
+        };
+
+        Poll::Ready(entry)
+    }
+}
+
+/// Merge two collated [`Stream`]s of `(key, value)` entries into one using the given
+/// `collator` for keys, calling `combine(left_value, right_value)` to compute the value
+/// of a key present in both streams.
+/// Both input streams **must** be collated by key.
+/// If either input stream is not collated, the order of the output stream is undefined.
+pub fn merge_kv<C, F, K, V, L, R>(
+    collator: C,
+    combine: F,
+    left: L,
+    right: R,
+) -> MergeKV<C, F, K, V, L, R>
+where
+    C: CollateRef<K>,
+    F: FnMut(V, V) -> V,
+    L: Stream<Item = (K, V)>,
+    R: Stream<Item = (K, V)>,
+{
+    MergeKV {
+        collator,
+        combine,
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+    }
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`merge_latest`].
+#[pin_project]
+pub struct MergeLatest<C, F, K, Ver, V, L, R> {
+    collator: C,
+    cmp_version: F,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<(K, Ver, V)>,
+    pending_right: Option<(K, Ver, V)>,
+    latest: Option<(K, Ver, V)>,
+}
+
+impl<C, F, K, Ver, V, L, R> Stream for MergeLatest<C, F, K, Ver, V, L, R>
+where
+    C: CollateRef<K>,
+    F: Fn(&Ver, &Ver) -> Ordering,
+    L: Stream<Item = (K, Ver, V)> + Unpin,
+    R: Stream<Item = (K, Ver, V)> + Unpin,
+{
+    type Item = (K, Ver, V);
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if !this.left.is_done() && this.pending_left.is_none() {
+                if let Some(record) = ready!(this.left.as_mut().poll_next(cxt)) {
+                    *this.pending_left = Some(record);
+                }
+            }
+
+            if !this.right.is_done() && this.pending_right.is_none() {
+                if let Some(record) = ready!(this.right.as_mut().poll_next(cxt)) {
+                    *this.pending_right = Some(record);
+                }
+            }
+
+            let next = match (this.pending_left.as_ref(), this.pending_right.as_ref()) {
+                (Some((l_key, _, _)), Some((r_key, _, _))) => {
+                    match this.collator.cmp_ref(l_key, r_key) {
+                        Ordering::Less | Ordering::Equal => this.pending_left.take(),
+                        Ordering::Greater => this.pending_right.take(),
+                    }
+                }
+                (Some(_), None) => this.pending_left.take(),
+                (None, Some(_)) => this.pending_right.take(),
+                (None, None) => None,
+            };
+
+            let next = match next {
+                Some(next) => next,
+                None => break this.latest.take(),
+            };
+
+            match this.latest.take() {
+                Some(latest) if this.collator.cmp_ref(&latest.0, &next.0) == Ordering::Equal => {
+                    // keep only the highest version of this key, preferring the first seen
+                    if (this.cmp_version)(&next.1, &latest.1) == Ordering::Greater {
+                        *this.latest = Some(next);
+                    } else {
+                        *this.latest = Some(latest);
+                    }
+                }
+                Some(latest) => {
+                    *this.latest = Some(next);
+                    break Some(latest);
+                }
+                None => {
+                    *this.latest = Some(next);
+                }
+            }
+        })
+    }
+}
+
+/// Merge two collated [`Stream`]s of `(key, version, value)` records into one using the given
+/// `collator` for keys, emitting only the record with the highest version of each key
+/// according to `cmp_version`. If two records have the same key and version, the first is kept.
+/// Both input streams **must** be collated by key.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn merge_latest<C, F, K, Ver, V, L, R>(
+    collator: C,
+    cmp_version: F,
+    left: L,
+    right: R,
+) -> MergeLatest<C, F, K, Ver, V, L, R>
+where
+    C: CollateRef<K>,
+    F: Fn(&Ver, &Ver) -> Ordering,
+    L: Stream<Item = (K, Ver, V)>,
+    R: Stream<Item = (K, Ver, V)>,
+{
+    MergeLatest {
+        collator,
+        cmp_version,
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+        latest: None,
+    }
+}
@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use pin_project::pin_project;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The progress of a [`MergeMany`] or [`TryMergeMany`](crate::TryMergeMany) stream,
+/// which can be persisted in order to resume a long-running merge after a restart.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MergeCheckpoint {
+    /// The number of items consumed from each input stream,
+    /// i.e. the position at which to resume reading each input stream
+    pub consumed: Vec<u64>,
+
+    /// The number of items emitted by the merged stream
+    pub emitted: u64,
+}
+
+type ProgressCallback<T> = Box<dyn FnMut(&MergeCheckpoint, &T) + Send>;
+
+/// A callback to report the progress of a [`MergeMany`] or [`TryMergeMany`](crate::TryMergeMany)
+/// stream, every `interval` items emitted.
+pub(super) struct Progress<T> {
+    interval: u64,
+    callback: ProgressCallback<T>,
+}
+
+impl<T> Progress<T> {
+    pub(super) fn new<F>(interval: u64, callback: F) -> Self
+    where
+        F: FnMut(&MergeCheckpoint, &T) + Send + 'static,
+    {
+        assert!(interval > 0, "progress interval must be nonzero");
+
+        Self {
+            interval,
+            callback: Box::new(callback),
+        }
+    }
+
+    // `u64::is_multiple_of` requires Rust 1.87, newer than the crate's `rust-version`
+    pub(super) fn report(&mut self, checkpoint: &MergeCheckpoint, item: &T) {
+        if checkpoint.emitted % self.interval == 0 {
+            (self.callback)(checkpoint, item)
+        }
+    }
+}
+
+/// The stream type returned by [`merge_many`].
+#[pin_project]
+pub struct MergeMany<C, T, S> {
+    collator: C,
+    sources: Vec<Fuse<S>>,
+    pending: Vec<Option<T>>,
+    checkpoint: MergeCheckpoint,
+    progress: Option<Progress<T>>,
+}
+
+impl<C, T, S> MergeMany<C, T, S> {
+    /// Return the current progress of this merge.
+    ///
+    /// An item which has been read from an input stream but not yet emitted or dropped
+    /// is not counted as consumed, so that it will be read again after resuming.
+    pub fn checkpoint(&self) -> &MergeCheckpoint {
+        &self.checkpoint
+    }
+
+    /// Call the given `callback` with the current [`MergeCheckpoint`] and the item just emitted
+    /// every `interval` items, for example to display a progress bar or to detect a stall.
+    ///
+    /// Panics if `interval` is zero.
+    pub fn on_progress<F>(mut self, interval: u64, callback: F) -> Self
+    where
+        F: FnMut(&MergeCheckpoint, &T) + Send + 'static,
+    {
+        self.progress = Some(Progress::new(interval, callback));
+        self
+    }
+}
+
+impl<C, T, S> Stream for MergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    S: Stream<Item = T> + Unpin,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        for (source, pending) in this.sources.iter_mut().zip(this.pending.iter_mut()) {
+            if !source.is_done() && pending.is_none() {
+                *pending = ready!(Pin::new(source).poll_next(cxt));
+            }
+        }
+
+        let next = take_min(this.collator, this.pending, this.checkpoint);
+
+        if let (Some(progress), Some(item)) = (this.progress.as_mut(), next.as_ref()) {
+            progress.report(this.checkpoint, item);
+        }
+
+        Poll::Ready(next)
+    }
+}
+
+/// Merge any number of collated [`Stream`]s into one using the given `collator`.
+/// All input streams **must** be collated.
+/// If any input stream is not collated, the order of the output stream is undefined.
+///
+/// Like [`merge`](crate::merge), an item which is equal to an item in an earlier input stream
+/// is dropped. The next item is found with a linear scan of the pending items,
+/// so this is intended for a modest number of input streams.
+pub fn merge_many<C, T, S, I>(collator: C, sources: I) -> MergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    S: Stream<Item = T>,
+    I: IntoIterator<Item = S>,
+{
+    let sources = sources.into_iter().collect::<Vec<S>>();
+    let checkpoint = MergeCheckpoint {
+        consumed: vec![0; sources.len()],
+        emitted: 0,
+    };
+
+    merge_many_resume(collator, sources, checkpoint)
+}
+
+/// Resume a merge of any number of collated [`Stream`]s from the given `checkpoint`.
+///
+/// Each input stream **must** be positioned at the number of items consumed from it
+/// according to the `checkpoint`, for example by seeking or by using `StreamExt::skip`.
+///
+/// Panics if the number of input streams does not match the `checkpoint`.
+pub fn merge_many_resume<C, T, S, I>(
+    collator: C,
+    sources: I,
+    checkpoint: MergeCheckpoint,
+) -> MergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    S: Stream<Item = T>,
+    I: IntoIterator<Item = S>,
+{
+    let sources = sources.into_iter().map(Fuse::new).collect::<Vec<Fuse<S>>>();
+
+    assert_eq!(
+        sources.len(),
+        checkpoint.consumed.len(),
+        "the number of streams to merge does not match the checkpoint"
+    );
+
+    let pending = std::iter::repeat_with(|| None)
+        .take(sources.len())
+        .collect();
+
+    MergeMany {
+        collator,
+        sources,
+        pending,
+        checkpoint,
+        progress: None,
+    }
+}
+
+/// Take the least pending item, dropping any other pending items which are equal to it,
+/// and record the items consumed in the given `checkpoint`.
+pub(super) fn take_min<C, T>(
+    collator: &C,
+    pending: &mut [Option<T>],
+    checkpoint: &mut MergeCheckpoint,
+) -> Option<T>
+where
+    C: CollateRef<T>,
+{
+    let mut min: Option<(usize, &T)> = None;
+
+    for (i, item) in pending.iter().enumerate() {
+        if let Some(item) = item {
+            match min {
+                Some((_, min)) if collator.cmp_ref(item, min) != Ordering::Less => {}
+                _ => min = Some((i, item)),
+            }
+        }
+    }
+
+    let (i, _) = min?;
+    let min = pending[i].take()?;
+    checkpoint.consumed[i] += 1;
+
+    for (i, item) in pending.iter_mut().enumerate() {
+        if let Some(value) = item {
+            if collator.cmp_ref(value, &min) == Ordering::Equal {
+                *item = None;
+                checkpoint.consumed[i] += 1;
+            }
+        }
+    }
+
+    checkpoint.emitted += 1;
+
+    Some(min)
+}
@@ -1,19 +1,59 @@
+pub use async_diff::*;
+pub use async_merge::*;
+pub use bisect::*;
 pub use diff::*;
+pub use expect::*;
+#[cfg(feature = "io")]
+pub use io::*;
+pub use join::*;
 pub use merge::*;
+pub use merge_kv::*;
+pub use merge_latest::*;
+pub use merge_many::*;
+pub use quantile::*;
+pub use rank::*;
 pub use try_diff::*;
+pub use try_join::*;
 pub use try_merge::*;
+pub use try_merge_kv::*;
+pub use try_merge_latest::*;
+pub use try_merge_many::*;
+pub use try_rank::*;
 
+mod async_diff;
+mod async_merge;
+mod bisect;
 mod diff;
+mod expect;
+mod fuse;
+#[cfg(feature = "io")]
+mod io;
+mod join;
 mod merge;
+mod merge_kv;
+mod merge_latest;
+mod merge_many;
+mod quantile;
+mod rank;
 mod try_diff;
+mod try_join;
 mod try_merge;
+mod try_merge_kv;
+mod try_merge_latest;
+mod try_merge_many;
+mod try_rank;
+
+use fuse::Fuse;
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Collator;
-    use futures::stream::{self, StreamExt, TryStreamExt};
+    use futures::stream::{self, Stream, StreamExt, TryStreamExt};
     use std::fmt;
+    use std::ops::Bound;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
 
     #[derive(Debug)]
     struct Error(String);
@@ -26,6 +66,65 @@ mod tests {
 
     impl std::error::Error for Error {}
 
+    impl From<crate::NanError> for Error {
+        fn from(cause: crate::NanError) -> Self {
+            Self(cause.to_string())
+        }
+    }
+
+    /// A stream which yields the given sequence of polls, waking itself after each `Pending`,
+    /// and continues to yield items after it ends.
+    struct Misbehaving<T> {
+        polls: std::collections::VecDeque<Poll<Option<T>>>,
+    }
+
+    impl<T> Misbehaving<T> {
+        fn new<I: IntoIterator<Item = Poll<Option<T>>>>(polls: I) -> Self {
+            Self {
+                polls: polls.into_iter().collect(),
+            }
+        }
+    }
+
+    impl<T: Unpin> Stream for Misbehaving<T> {
+        type Item = T;
+
+        fn poll_next(mut self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<T>> {
+            match self.polls.pop_front() {
+                Some(Poll::Pending) => {
+                    cxt.waker().wake_by_ref();
+                    Poll::Pending
+                }
+                Some(poll) => poll,
+                None => Poll::Ready(None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_try_bisect() {
+        let collator = Collator::<u32>::default();
+        let items = [1, 3, 3, 3, 5, 8];
+        let get = |i: usize| {
+            let item = items[i];
+            async move { Result::<u32, Error>::Ok(item) }
+        };
+
+        let bisect = |bound| try_bisect(&collator, items.len(), get, bound);
+
+        assert_eq!(bisect(Bound::Unbounded).await.expect("index"), 0);
+        assert_eq!(bisect(Bound::Included(&0)).await.expect("index"), 0);
+        assert_eq!(bisect(Bound::Included(&3)).await.expect("index"), 1);
+        assert_eq!(bisect(Bound::Excluded(&3)).await.expect("index"), 4);
+        assert_eq!(bisect(Bound::Included(&6)).await.expect("index"), 5);
+        assert_eq!(bisect(Bound::Excluded(&8)).await.expect("index"), 6);
+
+        let fail = |_| async { Result::<u32, Error>::Err(Error("io".to_string())) };
+        assert!(try_bisect(&collator, 1, fail, Bound::Included(&1))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_diff() {
         let collator = Collator::<u32>::default();
@@ -79,6 +178,44 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn test_merge_misbehaving() {
+        let collator = Collator::<u32>::default();
+
+        let left = Misbehaving::new([
+            Poll::Pending,
+            Poll::Ready(Some(1)),
+            Poll::Pending,
+            Poll::Ready(Some(4)),
+            Poll::Ready(None),
+            Poll::Ready(Some(0)),
+        ]);
+
+        let right = Misbehaving::new([
+            Poll::Ready(Some(2)),
+            Poll::Pending,
+            Poll::Pending,
+            Poll::Ready(Some(3)),
+            Poll::Ready(None),
+            Poll::Ready(Some(5)),
+        ]);
+
+        let actual = merge(collator, left, right).collect::<Vec<u32>>().await;
+        assert_eq!(actual, vec![1, 2, 3, 4]);
+
+        let left = Misbehaving::new([Poll::Pending, Poll::Ready(Some(Ok(1))), Poll::Ready(None)]);
+        let right = Misbehaving::new([
+            Poll::Ready(Some(Ok(1))),
+            Poll::Pending,
+            Poll::Ready(Some(Err(Error("misbehaving".to_string())))),
+        ]);
+
+        let mut merged = try_merge(collator, left, right);
+        assert_eq!(merged.try_next().await.expect("item"), Some(1));
+        assert!(merged.try_next().await.is_err());
+        assert_eq!(merged.try_next().await.expect("end"), None);
+    }
+
     #[tokio::test]
     async fn test_try_merge() {
         let collator = Collator::<u32>::default();
@@ -101,4 +238,415 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[tokio::test]
+    async fn test_try_collate_merge_and_diff() {
+        use crate::StrictFloatCollator;
+
+        let collator = StrictFloatCollator::<f64>::new();
+        let left = || stream::iter([1.0, 3.0, f64::NAN, 4.0]).map(Result::<f64, Error>::Ok);
+        let right = || stream::iter([2.0, 3.0, 5.0]).map(Result::<f64, Error>::Ok);
+
+        let merged = try_collate_merge(collator, left(), right())
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(merged.len(), 4);
+        assert_eq!(
+            merged[..3].iter().flatten().collect::<Vec<_>>(),
+            [&1.0, &2.0, &3.0]
+        );
+        assert_eq!(merged[3].as_ref().unwrap_err().0, "cannot collate NaN");
+
+        let diff = try_collate_diff(collator, left(), right());
+        let error = diff.try_collect::<Vec<_>>().await.unwrap_err();
+        assert_eq!(error.0, "cannot collate NaN");
+
+        let diff = try_collate_diff(collator, left().take(2), right());
+        assert_eq!(diff.try_collect::<Vec<_>>().await.expect("diff"), [1.0]);
+    }
+
+    #[tokio::test]
+    async fn test_async_merge_and_diff() {
+        #[derive(Eq, PartialEq)]
+        struct Yielding;
+
+        impl crate::AsyncCollate for Yielding {
+            type Value = u32;
+
+            async fn cmp(&self, left: &u32, right: &u32) -> std::cmp::Ordering {
+                tokio::task::yield_now().await;
+                left.cmp(right)
+            }
+        }
+
+        let left = || stream::iter(vec![1, 3, 5, 7, 8, 9, 20]);
+        let right = || stream::iter(vec![2, 4, 5, 6, 8, 9]);
+
+        let merged = async_merge(Yielding, left(), right()).collect::<Vec<u32>>();
+        assert_eq!(merged.await, [1, 2, 3, 4, 5, 6, 7, 8, 9, 20]);
+
+        let diff = async_diff(Yielding, left(), right()).collect::<Vec<u32>>();
+        assert_eq!(diff.await, [1, 3, 7, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_latest() {
+        let collator = Collator::<u32>::default();
+
+        let left = vec![(1, 1, 'a'), (2, 1, 'b'), (2, 3, 'c'), (4, 1, 'd')];
+        let right = vec![(2, 2, 'e'), (3, 1, 'f'), (4, 2, 'g')];
+
+        let expected = vec![(1, 1, 'a'), (2, 3, 'c'), (3, 1, 'f'), (4, 2, 'g')];
+        let actual = merge_latest(collator, u32::cmp, stream::iter(left), stream::iter(right))
+            .collect::<Vec<(u32, u32, char)>>()
+            .await;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_try_merge_latest() {
+        let collator = Collator::<u32>::default();
+
+        let left = vec![(1, 1, 'a'), (2, 1, 'b'), (2, 3, 'c'), (4, 1, 'd')];
+        let right = vec![(2, 2, 'e'), (3, 1, 'f'), (4, 2, 'g')];
+
+        let expected = vec![(1, 1, 'a'), (2, 3, 'c'), (3, 1, 'f'), (4, 2, 'g')];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_merge_latest(
+            collator,
+            u32::cmp,
+            stream::iter(left).map(Result::<(u32, u32, char), Error>::Ok),
+            stream::iter(right).map(Result::<(u32, u32, char), Error>::Ok),
+        );
+
+        while let Some(record) = stream.try_next().await.expect("record") {
+            actual.push(record);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_merge_kv() {
+        let collator = Collator::<&str>::default();
+
+        let left = vec![("a", 1), ("b", 2), ("d", 4)];
+        let right = vec![("b", 3), ("c", 5), ("d", 6), ("e", 7)];
+
+        let expected = vec![("a", 1), ("b", 5), ("c", 5), ("d", 10), ("e", 7)];
+        let actual = merge_kv(
+            collator,
+            |l, r| l + r,
+            stream::iter(left),
+            stream::iter(right),
+        )
+        .collect::<Vec<(&str, u32)>>()
+        .await;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_try_merge_kv() {
+        let collator = Collator::<&str>::default();
+
+        let left = vec![("a", 1), ("b", 2), ("d", 4)];
+        let right = vec![("b", 3), ("c", 5), ("d", 6), ("e", 7)];
+
+        let expected = vec![("a", 1), ("b", 3), ("c", 5), ("d", 6), ("e", 7)];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_merge_kv(
+            collator,
+            u32::max,
+            stream::iter(left).map(Result::<(&str, u32), Error>::Ok),
+            stream::iter(right).map(Result::<(&str, u32), Error>::Ok),
+        );
+
+        while let Some(entry) = stream.try_next().await.expect("entry") {
+            actual.push(entry);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_join() {
+        let collator = Collator::<u32>::default();
+
+        let entries = vec![(1, 'a'), (2, 'b'), (2, 'c'), (4, 'd'), (7, 'e'), (9, 'f')];
+        let keys = vec![2, 3, 7, 8];
+
+        let expected = vec![(1, 'a'), (4, 'd'), (9, 'f')];
+        let actual = anti_join(
+            collator,
+            stream::iter(entries.clone()),
+            stream::iter(keys.clone()),
+        )
+        .collect::<Vec<(u32, char)>>()
+        .await;
+
+        assert_eq!(expected, actual);
+
+        let expected = vec![(2, 'b'), (2, 'c'), (7, 'e')];
+        let actual = semi_join(collator, stream::iter(entries), stream::iter(keys))
+            .collect::<Vec<(u32, char)>>()
+            .await;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_try_join() {
+        let collator = Collator::<u32>::default();
+
+        let entries = vec![(1, 'a'), (2, 'b'), (2, 'c'), (4, 'd'), (7, 'e'), (9, 'f')];
+        let keys = vec![2, 3, 7, 8];
+
+        let expected = vec![(1, 'a'), (4, 'd'), (9, 'f')];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_anti_join(
+            collator,
+            stream::iter(entries.clone()).map(Result::<(u32, char), Error>::Ok),
+            stream::iter(keys.clone()).map(Result::<u32, Error>::Ok),
+        );
+
+        while let Some(entry) = stream.try_next().await.expect("entry") {
+            actual.push(entry);
+        }
+
+        assert_eq!(expected, actual);
+
+        let expected = vec![(2, 'b'), (2, 'c'), (7, 'e')];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_semi_join(
+            collator,
+            stream::iter(entries).map(Result::<(u32, char), Error>::Ok),
+            stream::iter(keys).map(Result::<u32, Error>::Ok),
+        );
+
+        while let Some(entry) = stream.try_next().await.expect("entry") {
+            actual.push(entry);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_merge_many() {
+        let collator = Collator::<u32>::default();
+
+        let sources = vec![
+            stream::iter(vec![1, 4, 7, 10]),
+            stream::iter(vec![]),
+            stream::iter(vec![2, 4, 8]),
+            stream::iter(vec![3, 4, 9, 10, 11]),
+        ];
+
+        let expected = vec![1, 2, 3, 4, 7, 8, 9, 10, 11];
+        let actual = merge_many(collator, sources).collect::<Vec<u32>>().await;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_merge_many_resume() {
+        let collator = Collator::<u32>::default();
+        let inputs = [vec![1, 4, 7, 10], vec![2, 4, 8], vec![3, 4, 9, 10, 11]];
+
+        let sources = inputs.iter().cloned().map(stream::iter);
+        let mut merged = merge_many(collator, sources);
+
+        let mut actual = Vec::new();
+        for _ in 0..5 {
+            actual.push(merged.next().await.expect("n"));
+        }
+
+        let checkpoint = merged.checkpoint().clone();
+        assert_eq!(checkpoint.consumed, vec![3, 2, 2]);
+        assert_eq!(checkpoint.emitted, 5);
+
+        let sources = inputs
+            .iter()
+            .cloned()
+            .zip(checkpoint.consumed.clone())
+            .map(|(input, consumed)| stream::iter(input).skip(consumed as usize));
+
+        let resumed = merge_many_resume(collator, sources, checkpoint);
+        actual.extend(resumed.collect::<Vec<u32>>().await);
+
+        assert_eq!(actual, vec![1, 2, 3, 4, 7, 8, 9, 10, 11]);
+    }
+
+    #[tokio::test]
+    async fn test_merge_many_progress() {
+        let collator = Collator::<u32>::default();
+        let left = stream::iter(vec![1, 3, 5, 7]);
+        let right = stream::iter(vec![2, 3, 4, 8]);
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let merged = merge_many(collator, [left, right]).on_progress(3, move |checkpoint, key| {
+            let report = (checkpoint.consumed.clone(), checkpoint.emitted, *key);
+            sender.send(report).expect("send");
+        });
+
+        let actual = merged.collect::<Vec<u32>>().await;
+        assert_eq!(actual, vec![1, 2, 3, 4, 5, 7, 8]);
+
+        let reports = receiver.try_iter().collect::<Vec<_>>();
+        assert_eq!(reports, vec![(vec![2, 2], 3, 3), (vec![4, 3], 6, 7)]);
+    }
+
+    #[tokio::test]
+    async fn test_try_merge_many() {
+        let collator = Collator::<u32>::default();
+
+        let sources = vec![
+            stream::iter(vec![1, 4, 7, 10]).map(Result::<u32, Error>::Ok),
+            stream::iter(vec![2, 4, 8]).map(Result::<u32, Error>::Ok),
+            stream::iter(vec![3, 4, 9, 10, 11]).map(Result::<u32, Error>::Ok),
+        ];
+
+        let expected = vec![1, 2, 3, 4, 7, 8, 9, 10, 11];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_merge_many(collator, sources);
+
+        while let Some(n) = stream.try_next().await.expect("n") {
+            actual.push(n);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[cfg(feature = "io")]
+    #[tokio::test]
+    async fn test_merge_readers() {
+        use futures::io::Cursor;
+
+        let collator = Collator::<String>::default();
+
+        let readers = vec![
+            Cursor::new(b"apple\ncherry\nfig\n".to_vec()),
+            Cursor::new(b"banana\r\ncherry\r\ngrape".to_vec()),
+            Cursor::new(b"".to_vec()),
+        ];
+
+        let expected = vec!["apple", "banana", "cherry", "fig", "grape"];
+        let actual = merge_readers(collator, LineDecoder, readers)
+            .try_collect::<Vec<String>>()
+            .await
+            .expect("lines");
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_quantiles() {
+        let values = vec![1, 2, 2, 2, 3, 4, 5, 6, 7, 100];
+        let fractions = [0.5, 0.0, 0.95, 0.25, 1.0];
+
+        let expected = vec![3, 1, 100, 2, 100];
+        let actual = quantiles(stream::iter(values.clone()), values.len(), &fractions).await;
+        assert_eq!(Some(expected.clone()), actual);
+
+        let actual = try_quantiles(
+            stream::iter(values.clone()).map(Result::<u32, Error>::Ok),
+            values.len(),
+            &fractions,
+        )
+        .await
+        .expect("quantiles");
+
+        assert_eq!(Some(expected), actual);
+
+        let actual = quantiles(stream::iter(values), 20, &[0.99]).await;
+        assert_eq!(None, actual);
+    }
+
+    #[tokio::test]
+    async fn test_rank() {
+        let collator = Collator::<u32>::default();
+        let scores = vec![10, 10, 20, 30, 30, 30, 40];
+
+        let expected = vec![1, 1, 3, 4, 4, 4, 7];
+        let actual = rank(collator, stream::iter(scores.clone()))
+            .map(|(rank, _score)| rank)
+            .collect::<Vec<usize>>()
+            .await;
+
+        assert_eq!(expected, actual);
+
+        let expected = vec![
+            (1, 10),
+            (1, 10),
+            (2, 20),
+            (3, 30),
+            (3, 30),
+            (3, 30),
+            (4, 40),
+        ];
+        let actual = dense_rank(collator, stream::iter(scores))
+            .collect::<Vec<(usize, u32)>>()
+            .await;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_try_rank() {
+        let collator = Collator::<u32>::default();
+        let scores = vec![10, 10, 20, 30, 30, 30, 40];
+
+        let expected = vec![1, 1, 3, 4, 4, 4, 7];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream = try_rank(
+            collator,
+            stream::iter(scores.clone()).map(Result::<u32, Error>::Ok),
+        );
+
+        while let Some((rank, _score)) = stream.try_next().await.expect("rank") {
+            actual.push(rank);
+        }
+
+        assert_eq!(expected, actual);
+
+        let expected = vec![1, 1, 2, 3, 3, 3, 4];
+        let mut actual = Vec::with_capacity(expected.len());
+
+        let mut stream =
+            try_dense_rank(collator, stream::iter(scores).map(Result::<u32, Error>::Ok));
+
+        while let Some((rank, _score)) = stream.try_next().await.expect("rank") {
+            actual.push(rank);
+        }
+
+        assert_eq!(expected, actual);
+    }
+
+    #[tokio::test]
+    async fn test_expect_collated() {
+        let collator = Collator::<u32>::default();
+
+        let items = expect_collated(collator, stream::iter(vec![1, 2, 2, 3]))
+            .collect::<Vec<u32>>()
+            .await;
+
+        assert_eq!(items, vec![1, 2, 2, 3]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "stream is not collated: 3 collates after 2")]
+    async fn test_expect_collated_panics() {
+        let collator = Collator::<u32>::default();
+        let stream = expect_collated(collator, stream::iter(vec![1, 3, 2]));
+        stream.collect::<Vec<u32>>().await;
+    }
 }
@@ -0,0 +1,126 @@
+use std::future::poll_fn;
+use std::pin::pin;
+
+use futures_core::{Stream, TryStream};
+
+use crate::sort::quantile_index;
+
+/// Compute the exact quantiles of a collated [`Stream`] of `len` items in a single pass,
+/// returning the item at each of the given `fractions` (e.g. `0.5` for the median) in the
+/// same order as `fractions`. Only the items up to the greatest requested quantile are read.
+///
+/// This uses the nearest-rank method, i.e. the quantile at fraction `p` is the item at
+/// 1-based position `ceil(p * len)` (or the first item, if `p` is zero), so it is always
+/// an item of the stream even if the stream contains duplicates.
+///
+/// Returns `None` if the stream ends before the greatest requested quantile.
+/// The input stream **must** be collated. If not, the quantiles returned are undefined.
+///
+/// Panics if any of the given `fractions` is not in the range `0.0..=1.0`.
+pub async fn quantiles<T, S>(source: S, len: usize, fractions: &[f64]) -> Option<Vec<T>>
+where
+    T: Clone,
+    S: Stream<Item = T>,
+{
+    let indices = quantile_indices(len, fractions)?;
+    let mut values = Vec::with_capacity(fractions.len());
+
+    let limit = indices.last().map(|i| i + 1).unwrap_or_default();
+    let mut source = pin!(source);
+
+    let mut position = 0;
+    while position < limit {
+        match poll_fn(|cxt| source.as_mut().poll_next(cxt)).await {
+            Some(item) => collect(&indices, &mut values, position, item),
+            None => break,
+        }
+
+        position += 1;
+    }
+
+    finish(len, &indices, values, fractions)
+}
+
+/// Compute the exact quantiles of a collated [`TryStream`] of `len` items in a single pass.
+/// See [`quantiles`] for details.
+pub async fn try_quantiles<T, E, S>(
+    source: S,
+    len: usize,
+    fractions: &[f64],
+) -> Result<Option<Vec<T>>, E>
+where
+    T: Clone,
+    S: TryStream<Ok = T, Error = E>,
+{
+    let indices = match quantile_indices(len, fractions) {
+        Some(indices) => indices,
+        None => return Ok(None),
+    };
+
+    let mut values = Vec::with_capacity(fractions.len());
+
+    let limit = indices.last().map(|i| i + 1).unwrap_or_default();
+    let mut source = pin!(source);
+
+    let mut position = 0;
+    while position < limit {
+        match poll_fn(|cxt| source.as_mut().try_poll_next(cxt)).await {
+            Some(Ok(item)) => collect(&indices, &mut values, position, item),
+            Some(Err(cause)) => return Err(cause),
+            None => break,
+        }
+
+        position += 1;
+    }
+
+    Ok(finish(len, &indices, values, fractions))
+}
+
+// return the distinct indices to read, in order
+fn quantile_indices(len: usize, fractions: &[f64]) -> Option<Vec<usize>> {
+    if len == 0 {
+        return if fractions.is_empty() {
+            Some(vec![])
+        } else {
+            None
+        };
+    }
+
+    let mut indices = fractions
+        .iter()
+        .map(|fraction| quantile_index(len, *fraction))
+        .collect::<Vec<usize>>();
+
+    indices.sort();
+    indices.dedup();
+
+    Some(indices)
+}
+
+// collect the item at the given position, if it is one of the requested quantiles
+fn collect<T>(indices: &[usize], values: &mut Vec<T>, position: usize, item: T) {
+    if indices.binary_search(&position).is_ok() {
+        values.push(item);
+    }
+}
+
+// map the distinct quantile values back to the order of the requested fractions
+fn finish<T: Clone>(
+    len: usize,
+    indices: &[usize],
+    values: Vec<T>,
+    fractions: &[f64],
+) -> Option<Vec<T>> {
+    if values.len() < indices.len() {
+        return None;
+    }
+
+    let quantiles = fractions
+        .iter()
+        .map(|fraction| quantile_index(len, *fraction))
+        .map(|index| indices.binary_search(&index).expect("quantile"))
+        .map(|i| values[i].clone())
+        .collect();
+
+    Some(quantiles)
+}
@@ -2,10 +2,11 @@ use std::cmp::Ordering;
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use futures::stream::{Fuse, Stream, StreamExt, TryStream};
+use futures_core::{Stream, TryStream};
 use pin_project::pin_project;
 
-use crate::CollateRef;
+use super::Fuse;
+use crate::{CollateRef, TryCollate};
 
 /// The stream type returned by [`diff`].
 /// The implementation of this stream is based on
@@ -27,71 +28,146 @@ impl<C, T, E, L, R> Stream for TryDiff<C, T, L, R>
 where
     C: CollateRef<T>,
     E: std::error::Error,
-    Fuse<L>: TryStream<Ok = T, Error = E> + Unpin,
-    Fuse<R>: TryStream<Ok = T, Error = E> + Unpin,
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
 {
     type Item = Result<T, E>;
 
     fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-
-        Poll::Ready(loop {
-            let left_done = if this.left.is_done() {
-                true
-            } else if this.pending_left.is_none() {
-                match ready!(this.left.as_mut().try_poll_next(cxt)) {
-                    Some(Ok(value)) => {
-                        *this.pending_left = Some(value);
-                        false
-                    }
-                    Some(Err(cause)) => break Some(Err(cause)),
-                    None => true,
+        let this = self.project();
+        let collator = this.collator;
+
+        poll_try_diff(
+            this.left,
+            this.right,
+            this.pending_left,
+            this.pending_right,
+            cxt,
+            |l, r| Ok(collator.cmp_ref(l, r)),
+        )
+    }
+}
+
+/// The stream type returned by [`try_collate_diff`].
+#[pin_project]
+pub struct TryCollateDiff<C, T, L, R> {
+    collator: C,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<T>,
+    pending_right: Option<T>,
+    failed: bool,
+}
+
+impl<C, T, E, L, R> Stream for TryCollateDiff<C, T, L, R>
+where
+    C: TryCollate<Value = T>,
+    E: From<C::Error>,
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.failed {
+            return Poll::Ready(None);
+        }
+
+        let collator = this.collator;
+        let failed = this.failed;
+
+        poll_try_diff(
+            this.left,
+            this.right,
+            this.pending_left,
+            this.pending_right,
+            cxt,
+            |l, r| {
+                collator.try_cmp(l, r).map_err(|cause| {
+                    *failed = true;
+                    E::from(cause)
+                })
+            },
+        )
+    }
+}
+
+fn poll_try_diff<T, E, L, R, F>(
+    mut left: Pin<&mut Fuse<L>>,
+    mut right: Pin<&mut Fuse<R>>,
+    pending_left: &mut Option<T>,
+    pending_right: &mut Option<T>,
+    cxt: &mut Context,
+    mut cmp: F,
+) -> Poll<Option<Result<T, E>>>
+where
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
+    F: FnMut(&T, &T) -> Result<Ordering, E>,
+{
+    Poll::Ready(loop {
+        let left_done = if left.is_done() {
+            true
+        } else if pending_left.is_none() {
+            match ready!(left.as_mut().try_poll_next(cxt)) {
+                Some(Ok(value)) => {
+                    *pending_left = Some(value);
+                    false
+                }
+                Some(Err(cause)) => break Some(Err(cause)),
+                None => true,
+            }
+        } else {
+            false
+        };
+
+        let right_done = if right.is_done() {
+            true
+        } else if pending_right.is_none() {
+            match ready!(right.as_mut().try_poll_next(cxt)) {
+                Some(Ok(value)) => {
+                    *pending_right = Some(value);
+                    false
+                }
+                Some(Err(cause)) => break Some(Err(cause)),
+                None => true,
+            }
+        } else {
+            false
+        };
+
+        if pending_left.is_some() && pending_right.is_some() {
+            let l_value = pending_left.as_ref().unwrap();
+            let r_value = pending_right.as_ref().unwrap();
+
+            match cmp(l_value, r_value) {
+                Ok(Ordering::Equal) => {
+                    // this value is present in the right stream, so drop it
+                    pending_left.take();
+                    pending_right.take();
                 }
-            } else {
-                false
-            };
-
-            let right_done = if this.right.is_done() {
-                true
-            } else if this.pending_right.is_none() {
-                match ready!(this.right.as_mut().try_poll_next(cxt)) {
-                    Some(Ok(value)) => {
-                        *this.pending_right = Some(value);
-                        false
-                    }
-                    Some(Err(cause)) => break Some(Err(cause)),
-                    None => true,
+                Ok(Ordering::Less) => {
+                    // this value is not present in the right stream, so return it
+                    break pending_left.take().map(Ok);
                 }
-            } else {
-                false
-            };
-
-            if this.pending_left.is_some() && this.pending_right.is_some() {
-                let l_value = this.pending_left.as_ref().unwrap();
-                let r_value = this.pending_right.as_ref().unwrap();
-
-                match this.collator.cmp_ref(l_value, r_value) {
-                    Ordering::Equal => {
-                        // this value is present in the right stream, so drop it
-                        this.pending_left.take();
-                        this.pending_right.take();
-                    }
-                    Ordering::Less => {
-                        // this value is not present in the right stream, so return it
-                        break this.pending_left.take().map(Ok);
-                    }
-                    Ordering::Greater => {
-                        // this value could be present in the right stream--wait and see
-                        this.pending_right.take();
-                    }
+                Ok(Ordering::Greater) => {
+                    // this value could be present in the right stream--wait and see
+                    pending_right.take();
                 }
-            } else if right_done && this.pending_left.is_some() {
-                break this.pending_left.take().map(Ok);
-            } else if left_done {
-                break None;
+                Err(cause) => break Some(Err(cause)),
             }
-        })
-    }
+        } else if right_done && pending_left.is_some() {
+            break pending_left.take().map(Ok);
+        } else if left_done {
+            break None;
+        }
+    })
 }
 
 /// Compute the difference of two collated [`TryStream`]s,
@@ -107,9 +183,32 @@ where
 {
     TryDiff {
         collator,
-        left: left.fuse(),
-        right: right.fuse(),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+    }
+}
+
+/// Compute the difference of two collated [`TryStream`]s using the given fallible `collator`,
+/// i.e. return the items in `left` that are not in `right`.
+/// Both input streams **must** be collated.
+///
+/// If the `collator` fails to collate two values, the error is converted into the error type
+/// of the input streams and yielded by the output stream, which then ends.
+pub fn try_collate_diff<C, T, E, L, R>(collator: C, left: L, right: R) -> TryCollateDiff<C, T, L, R>
+where
+    C: TryCollate<Value = T>,
+    E: From<C::Error>,
+    L: TryStream<Ok = T, Error = E>,
+    R: TryStream<Ok = T, Error = E>,
+{
+    TryCollateDiff {
+        collator,
+        left: Fuse::new(left),
+        right: Fuse::new(right),
         pending_left: None,
         pending_right: None,
+        failed: false,
     }
 }
@@ -0,0 +1,126 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::{Stream, TryStream};
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`try_anti_join`] and [`try_semi_join`].
+#[pin_project]
+pub struct TryJoin<C, K, V, L, R> {
+    collator: C,
+    keep_present: bool,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<(K, V)>,
+    pending_right: Option<K>,
+}
+
+impl<C, K, V, E, L, R> Stream for TryJoin<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    L: TryStream<Ok = (K, V), Error = E> + Unpin,
+    R: TryStream<Ok = K, Error = E> + Unpin,
+{
+    type Item = Result<(K, V), E>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if !this.left.is_done() && this.pending_left.is_none() {
+                match ready!(this.left.as_mut().try_poll_next(cxt)) {
+                    Some(Ok(entry)) => *this.pending_left = Some(entry),
+                    Some(Err(cause)) => break Some(Err(cause)),
+                    None => {}
+                }
+            }
+
+            if !this.right.is_done() && this.pending_right.is_none() {
+                match ready!(this.right.as_mut().try_poll_next(cxt)) {
+                    Some(Ok(key)) => *this.pending_right = Some(key),
+                    Some(Err(cause)) => break Some(Err(cause)),
+                    None => {}
+                }
+            }
+
+            match (this.pending_left.as_ref(), this.pending_right.as_ref()) {
+                (Some((l_key, _)), Some(r_key)) => match this.collator.cmp_ref(l_key, r_key) {
+                    Ordering::Equal => {
+                        // this key is present in the right stream--keep it pending in case
+                        // the left stream has another entry with the same key
+                        let entry = this.pending_left.take();
+
+                        if *this.keep_present {
+                            break entry.map(Ok);
+                        }
+                    }
+                    Ordering::Less => {
+                        // this key is not present in the right stream
+                        let entry = this.pending_left.take();
+
+                        if !*this.keep_present {
+                            break entry.map(Ok);
+                        }
+                    }
+                    Ordering::Greater => {
+                        // this key could be present in the right stream--wait and see
+                        this.pending_right.take();
+                    }
+                },
+                (Some(_), None) if *this.keep_present => break None,
+                (Some(_), None) => break this.pending_left.take().map(Ok),
+                (None, _) => break None,
+            }
+        })
+    }
+}
+
+/// Filter a collated [`TryStream`] of `(key, value)` entries to only those whose key is
+/// **not** present in the collated `keys` stream, using the given `collator` for keys.
+/// Both input streams **must** be collated and have the same error type.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn try_anti_join<C, K, V, E, L, R>(collator: C, entries: L, keys: R) -> TryJoin<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    E: std::error::Error,
+    L: TryStream<Ok = (K, V), Error = E>,
+    R: TryStream<Ok = K, Error = E>,
+{
+    TryJoin {
+        collator,
+        keep_present: false,
+        left: Fuse::new(entries),
+        right: Fuse::new(keys),
+        pending_left: None,
+        pending_right: None,
+    }
+}
+
+/// Filter a collated [`TryStream`] of `(key, value)` entries to only those whose key is
+/// present in the collated `keys` stream, using the given `collator` for keys.
+/// Both input streams **must** be collated and have the same error type.
+/// If either input stream is not collated, the behavior of the output stream is undefined.
+pub fn try_semi_join<C, K, V, E, L, R>(collator: C, entries: L, keys: R) -> TryJoin<C, K, V, L, R>
+where
+    C: CollateRef<K>,
+    E: std::error::Error,
+    L: TryStream<Ok = (K, V), Error = E>,
+    R: TryStream<Ok = K, Error = E>,
+{
+    TryJoin {
+        collator,
+        keep_present: true,
+        left: Fuse::new(entries),
+        right: Fuse::new(keys),
+        pending_left: None,
+        pending_right: None,
+    }
+}
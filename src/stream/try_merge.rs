@@ -2,10 +2,11 @@ use std::cmp::Ordering;
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use futures::stream::{Fuse, Stream, StreamExt, TryStream};
+use futures_core::{Stream, TryStream};
 use pin_project::pin_project;
 
-use crate::CollateRef;
+use super::Fuse;
+use crate::{CollateRef, TryCollate};
 
 /// The stream returned by [`merge`].
 /// The implementation of this stream is based on
@@ -26,70 +27,128 @@ pub struct TryMerge<C, T, L, R> {
 impl<C, T, E, L, R> Stream for TryMerge<C, T, L, R>
 where
     C: CollateRef<T>,
-    Fuse<L>: TryStream<Ok = T, Error = E> + Unpin,
-    Fuse<R>: TryStream<Ok = T, Error = E> + Unpin,
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
 {
     type Item = Result<T, E>;
 
     fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
         let this = self.project();
+        let collator = this.collator;
 
-        let left_done = if this.left.is_done() {
-            true
-        } else if this.pending_left.is_none() {
-            match ready!(this.left.try_poll_next(cxt)) {
-                Some(Ok(value)) => {
-                    *this.pending_left = Some(value);
-                    false
-                }
-                Some(Err(cause)) => return Poll::Ready(Some(Err(cause))),
-                None => true,
-            }
-        } else {
-            false
-        };
-
-        let right_done = if this.right.is_done() {
-            true
-        } else if this.pending_right.is_none() {
-            match ready!(this.right.try_poll_next(cxt)) {
-                Some(Ok(value)) => {
-                    *this.pending_right = Some(value);
-                    false
-                }
-                Some(Err(cause)) => return Poll::Ready(Some(Err(cause))),
-                None => true,
-            }
-        } else {
-            false
-        };
-
-        let value = if this.pending_left.is_some() && this.pending_right.is_some() {
-            let l_value = this.pending_left.as_ref().unwrap();
-            let r_value = this.pending_right.as_ref().unwrap();
-
-            match this.collator.cmp_ref(l_value, r_value) {
-                Ordering::Equal => {
-                    this.pending_right.take();
-                    this.pending_left.take()
-                }
-                Ordering::Less => this.pending_left.take(),
-                Ordering::Greater => this.pending_right.take(),
-            }
-        } else if right_done && this.pending_left.is_some() {
-            this.pending_left.take()
-        } else if left_done && this.pending_right.is_some() {
-            this.pending_right.take()
-        } else if left_done && right_done {
-            None
-        } else {
-            unreachable!("both streams to merge are still pending")
-        };
-
-        Poll::Ready(value.map(Ok))
+        poll_try_merge(
+            this.left,
+            this.right,
+            this.pending_left,
+            this.pending_right,
+            cxt,
+            |l, r| Ok(collator.cmp_ref(l, r)),
+        )
     }
 }
 
+/// The stream returned by [`try_collate_merge`].
+#[pin_project]
+pub struct TryCollateMerge<C, T, L, R> {
+    collator: C,
+
+    #[pin]
+    left: Fuse<L>,
+    #[pin]
+    right: Fuse<R>,
+
+    pending_left: Option<T>,
+    pending_right: Option<T>,
+    failed: bool,
+}
+
+impl<C, T, E, L, R> Stream for TryCollateMerge<C, T, L, R>
+where
+    C: TryCollate<Value = T>,
+    E: From<C::Error>,
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        if *this.failed {
+            return Poll::Ready(None);
+        }
+
+        let collator = this.collator;
+        let failed = this.failed;
+
+        poll_try_merge(
+            this.left,
+            this.right,
+            this.pending_left,
+            this.pending_right,
+            cxt,
+            |l, r| {
+                collator.try_cmp(l, r).map_err(|cause| {
+                    *failed = true;
+                    E::from(cause)
+                })
+            },
+        )
+    }
+}
+
+fn poll_try_merge<T, E, L, R, F>(
+    mut left: Pin<&mut Fuse<L>>,
+    mut right: Pin<&mut Fuse<R>>,
+    pending_left: &mut Option<T>,
+    pending_right: &mut Option<T>,
+    cxt: &mut Context,
+    cmp: F,
+) -> Poll<Option<Result<T, E>>>
+where
+    L: TryStream<Ok = T, Error = E> + Unpin,
+    R: TryStream<Ok = T, Error = E> + Unpin,
+    F: FnOnce(&T, &T) -> Result<Ordering, E>,
+{
+    if !left.is_done() && pending_left.is_none() {
+        match ready!(left.as_mut().try_poll_next(cxt)) {
+            Some(Ok(value)) => *pending_left = Some(value),
+            Some(Err(cause)) => return Poll::Ready(Some(Err(cause))),
+            None => {}
+        }
+    }
+
+    if !right.is_done() && pending_right.is_none() {
+        match ready!(right.as_mut().try_poll_next(cxt)) {
+            Some(Ok(value)) => *pending_right = Some(value),
+            Some(Err(cause)) => return Poll::Ready(Some(Err(cause))),
+            None => {}
+        }
+    }
+
+    let value = match (pending_left.as_ref(), pending_right.as_ref()) {
+        (Some(l_value), Some(r_value)) => match cmp(l_value, r_value) {
+            Ok(Ordering::Equal) => {
+                pending_right.take();
+                pending_left.take()
+            }
+            Ok(Ordering::Less) => pending_left.take(),
+            Ok(Ordering::Greater) => pending_right.take(),
+            Err(cause) => return Poll::Ready(Some(Err(cause))),
+        },
+        (Some(_), None) if right.is_done() => pending_left.take(),
+        (None, Some(_)) if left.is_done() => pending_right.take(),
+        (None, None) if left.is_done() && right.is_done() => None,
+        _ => {
+            // an input stream neither yielded an item nor finished, so try again later
+            cxt.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+    };
+
+    Poll::Ready(value.map(Ok))
+}
+
 /// Merge two collated [`TryStream`]s into one using the given `collator`.
 /// Both input streams **must** be collated and have the same error type.
 /// If either input stream is not collated, the order of the output stream is undefined.
@@ -102,9 +161,35 @@ where
 {
     TryMerge {
         collator,
-        left: left.fuse(),
-        right: right.fuse(),
+        left: Fuse::new(left),
+        right: Fuse::new(right),
+        pending_left: None,
+        pending_right: None,
+    }
+}
+
+/// Merge two collated [`TryStream`]s into one using the given fallible `collator`.
+/// Both input streams **must** be collated and have the same error type.
+///
+/// If the `collator` fails to collate two values, the error is converted into the error type
+/// of the input streams and yielded by the output stream, which then ends.
+pub fn try_collate_merge<C, T, E, L, R>(
+    collator: C,
+    left: L,
+    right: R,
+) -> TryCollateMerge<C, T, L, R>
+where
+    C: TryCollate<Value = T>,
+    E: From<C::Error>,
+    L: TryStream<Ok = T, Error = E>,
+    R: TryStream<Ok = T, Error = E>,
+{
+    TryCollateMerge {
+        collator,
+        left: Fuse::new(left),
+        right: Fuse::new(right),
         pending_left: None,
         pending_right: None,
+        failed: false,
     }
 }
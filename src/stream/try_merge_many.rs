@@ -0,0 +1,123 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::{Stream, TryStream};
+use pin_project::pin_project;
+
+use super::merge_many::{take_min, MergeCheckpoint, Progress};
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`try_merge_many`].
+#[pin_project]
+pub struct TryMergeMany<C, T, S> {
+    collator: C,
+    sources: Vec<Fuse<S>>,
+    pending: Vec<Option<T>>,
+    checkpoint: MergeCheckpoint,
+    progress: Option<Progress<T>>,
+}
+
+impl<C, T, S> TryMergeMany<C, T, S> {
+    /// Return the current progress of this merge.
+    /// See [`MergeMany::checkpoint`](crate::MergeMany::checkpoint) for details.
+    pub fn checkpoint(&self) -> &MergeCheckpoint {
+        &self.checkpoint
+    }
+
+    /// Call the given `callback` with the current [`MergeCheckpoint`] and the item just emitted
+    /// every `interval` items.
+    /// See [`MergeMany::on_progress`](crate::MergeMany::on_progress) for details.
+    pub fn on_progress<F>(mut self, interval: u64, callback: F) -> Self
+    where
+        F: FnMut(&MergeCheckpoint, &T) + Send + 'static,
+    {
+        self.progress = Some(Progress::new(interval, callback));
+        self
+    }
+}
+
+impl<C, T, E, S> Stream for TryMergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    S: TryStream<Ok = T, Error = E> + Unpin,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+
+        for (source, pending) in this.sources.iter_mut().zip(this.pending.iter_mut()) {
+            if !source.is_done() && pending.is_none() {
+                match ready!(Pin::new(source).try_poll_next(cxt)) {
+                    Some(Ok(item)) => *pending = Some(item),
+                    Some(Err(cause)) => return Poll::Ready(Some(Err(cause))),
+                    None => {}
+                }
+            }
+        }
+
+        let next = take_min(this.collator, this.pending, this.checkpoint);
+
+        if let (Some(progress), Some(item)) = (this.progress.as_mut(), next.as_ref()) {
+            progress.report(this.checkpoint, item);
+        }
+
+        Poll::Ready(next.map(Ok))
+    }
+}
+
+/// Merge any number of collated [`TryStream`]s into one using the given `collator`.
+/// All input streams **must** be collated and have the same error type.
+/// If any input stream is not collated, the order of the output stream is undefined.
+///
+/// See [`merge_many`](crate::merge_many) for details.
+pub fn try_merge_many<C, T, E, S, I>(collator: C, sources: I) -> TryMergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    E: std::error::Error,
+    S: TryStream<Ok = T, Error = E>,
+    I: IntoIterator<Item = S>,
+{
+    let sources = sources.into_iter().collect::<Vec<S>>();
+    let checkpoint = MergeCheckpoint {
+        consumed: vec![0; sources.len()],
+        emitted: 0,
+    };
+
+    try_merge_many_resume(collator, sources, checkpoint)
+}
+
+/// Resume a merge of any number of collated [`TryStream`]s from the given `checkpoint`.
+/// See [`merge_many_resume`](crate::merge_many_resume) for details.
+pub fn try_merge_many_resume<C, T, E, S, I>(
+    collator: C,
+    sources: I,
+    checkpoint: MergeCheckpoint,
+) -> TryMergeMany<C, T, S>
+where
+    C: CollateRef<T>,
+    E: std::error::Error,
+    S: TryStream<Ok = T, Error = E>,
+    I: IntoIterator<Item = S>,
+{
+    let sources = sources.into_iter().map(Fuse::new).collect::<Vec<Fuse<S>>>();
+
+    assert_eq!(
+        sources.len(),
+        checkpoint.consumed.len(),
+        "the number of streams to merge does not match the checkpoint"
+    );
+
+    let pending = std::iter::repeat_with(|| None)
+        .take(sources.len())
+        .collect();
+
+    TryMergeMany {
+        collator,
+        sources,
+        pending,
+        checkpoint,
+        progress: None,
+    }
+}
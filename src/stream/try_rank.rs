@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::{Stream, TryStream};
+use pin_project::pin_project;
+
+use super::Fuse;
+use crate::CollateRef;
+
+/// The stream type returned by [`try_rank`] and [`try_dense_rank`].
+#[pin_project]
+pub struct TryRank<C, T, S> {
+    collator: C,
+    dense: bool,
+
+    #[pin]
+    source: Fuse<S>,
+
+    count: usize,
+    pending: Option<(usize, T)>,
+}
+
+impl<C, T, E, S> Stream for TryRank<C, T, S>
+where
+    C: CollateRef<T>,
+    S: TryStream<Ok = T, Error = E> + Unpin,
+{
+    type Item = Result<(usize, T), E>;
+
+    fn poll_next(self: Pin<&mut Self>, cxt: &mut Context) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        Poll::Ready(loop {
+            if this.source.is_done() {
+                break this.pending.take().map(Ok);
+            }
+
+            // the rank of the next item depends on the pending item, so wait to emit it
+            let next = match ready!(this.source.as_mut().try_poll_next(cxt)) {
+                Some(Ok(next)) => next,
+                Some(Err(cause)) => break Some(Err(cause)),
+                None => break this.pending.take().map(Ok),
+            };
+
+            *this.count += 1;
+
+            match this.pending.take() {
+                None => *this.pending = Some((1, next)),
+                Some((rank, item)) => {
+                    let next_rank = if this.collator.cmp_ref(&item, &next) == Ordering::Equal {
+                        rank
+                    } else if *this.dense {
+                        rank + 1
+                    } else {
+                        *this.count
+                    };
+
+                    *this.pending = Some((next_rank, next));
+                    break Some(Ok((rank, item)));
+                }
+            }
+        })
+    }
+}
+
+/// Assign a rank to each item in a collated [`TryStream`] using the given `collator`,
+/// where equal items share the same rank and leave a gap in the ranks which follow them,
+/// like the SQL `RANK()` function. Ranks start at 1.
+/// The input stream **must** be collated.
+/// If the input stream is not collated, the ranks of the output stream are undefined.
+pub fn try_rank<C, T, E, S>(collator: C, source: S) -> TryRank<C, T, S>
+where
+    C: CollateRef<T>,
+    E: std::error::Error,
+    S: TryStream<Ok = T, Error = E>,
+{
+    TryRank {
+        collator,
+        dense: false,
+        source: Fuse::new(source),
+        count: 0,
+        pending: None,
+    }
+}
+
+/// Assign a rank to each item in a collated [`TryStream`] using the given `collator`,
+/// where equal items share the same rank without leaving a gap in the ranks which follow
+/// them, like the SQL `DENSE_RANK()` function. Ranks start at 1.
+/// The input stream **must** be collated.
+/// If the input stream is not collated, the ranks of the output stream are undefined.
+pub fn try_dense_rank<C, T, E, S>(collator: C, source: S) -> TryRank<C, T, S>
+where
+    C: CollateRef<T>,
+    E: std::error::Error,
+    S: TryStream<Ok = T, Error = E>,
+{
+    TryRank {
+        collator,
+        dense: true,
+        source: Fuse::new(source),
+        count: 0,
+        pending: None,
+    }
+}
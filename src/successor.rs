@@ -0,0 +1,168 @@
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+use crate::{Collate, Collator, DirectedCollator, Direction};
+
+/// A collator for a discrete type, which can compute the value which immediately follows
+/// a given value in its collation.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, Successor};
+///
+/// let collator = Collator::<u8>::default();
+/// assert_eq!(collator.next_value(&1), Some(2));
+/// assert_eq!(collator.next_value(&u8::MAX), None);
+/// assert!(collator.is_adjacent(&1, &2));
+///
+/// assert_eq!(collator.include_start(Bound::Excluded(1)), Some(Bound::Included(2)));
+/// assert_eq!(collator.include_start(Bound::Excluded(u8::MAX)), None);
+/// ```
+pub trait Successor: Collate {
+    /// Return the value which immediately follows the given `value`, if any.
+    fn next_value(&self, value: &Self::Value) -> Option<Self::Value>;
+
+    /// Return `true` if `right` immediately follows `left`.
+    fn is_adjacent(&self, left: &Self::Value, right: &Self::Value) -> bool {
+        self.next_value(left)
+            .map(|next| self.cmp(&next, right) == Ordering::Equal)
+            .unwrap_or(false)
+    }
+
+    /// Convert an excluded start bound to the equivalent included start bound.
+    /// Returns `None` if no value follows an excluded start bound, i.e. if the range is empty.
+    fn include_start(&self, bound: Bound<Self::Value>) -> Option<Bound<Self::Value>> {
+        match bound {
+            Bound::Excluded(value) => self.next_value(&value).map(Bound::Included),
+            bound => Some(bound),
+        }
+    }
+}
+
+/// A collator for a discrete type, which can compute the value which immediately precedes
+/// a given value in its collation.
+///
+/// Example:
+/// ```
+/// use std::ops::Bound;
+/// use collate::{Collator, Predecessor};
+///
+/// let collator = Collator::<char>::default();
+/// assert_eq!(collator.prev_value(&'b'), Some('a'));
+/// assert_eq!(collator.prev_value(&'\u{E000}'), Some('\u{D7FF}'));
+/// assert_eq!(collator.include_end(Bound::Excluded('\0')), None);
+/// ```
+pub trait Predecessor: Collate {
+    /// Return the value which immediately precedes the given `value`, if any.
+    fn prev_value(&self, value: &Self::Value) -> Option<Self::Value>;
+
+    /// Convert an excluded end bound to the equivalent included end bound.
+    /// Returns `None` if no value precedes an excluded end bound, i.e. if the range is empty.
+    fn include_end(&self, bound: Bound<Self::Value>) -> Option<Bound<Self::Value>> {
+        match bound {
+            Bound::Excluded(value) => self.prev_value(&value).map(Bound::Included),
+            bound => Some(bound),
+        }
+    }
+}
+
+macro_rules! discrete_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Successor for Collator<$t> {
+                fn next_value(&self, value: &$t) -> Option<$t> {
+                    value.checked_add(1)
+                }
+            }
+
+            impl Predecessor for Collator<$t> {
+                fn prev_value(&self, value: &$t) -> Option<$t> {
+                    value.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+discrete_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Successor for Collator<bool> {
+    fn next_value(&self, value: &bool) -> Option<bool> {
+        if *value {
+            None
+        } else {
+            Some(true)
+        }
+    }
+}
+
+impl Predecessor for Collator<bool> {
+    fn prev_value(&self, value: &bool) -> Option<bool> {
+        if *value {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+impl Successor for Collator<char> {
+    fn next_value(&self, value: &char) -> Option<char> {
+        // skip the surrogate code points, which are not valid chars
+        match *value {
+            '\u{D7FF}' => Some('\u{E000}'),
+            c => char::from_u32(c as u32 + 1),
+        }
+    }
+}
+
+impl Predecessor for Collator<char> {
+    fn prev_value(&self, value: &char) -> Option<char> {
+        match *value {
+            '\u{E000}' => Some('\u{D7FF}'),
+            c => (c as u32).checked_sub(1).and_then(char::from_u32),
+        }
+    }
+}
+
+impl<C: Successor + Predecessor> Successor for DirectedCollator<C> {
+    fn next_value(&self, value: &Self::Value) -> Option<Self::Value> {
+        match self.direction() {
+            Direction::Ascending => self.inner().next_value(value),
+            Direction::Descending => self.inner().prev_value(value),
+        }
+    }
+}
+
+impl<C: Successor + Predecessor> Predecessor for DirectedCollator<C> {
+    fn prev_value(&self, value: &Self::Value) -> Option<Self::Value> {
+        match self.direction() {
+            Direction::Ascending => self.inner().prev_value(value),
+            Direction::Descending => self.inner().next_value(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_successor() {
+        let descending = DirectedCollator::new(Collator::<i8>::default(), Direction::Descending);
+        assert_eq!(descending.next_value(&0), Some(-1));
+        assert_eq!(descending.next_value(&i8::MIN), None);
+        assert_eq!(descending.prev_value(&0), Some(1));
+        assert!(descending.is_adjacent(&1, &0));
+
+        assert_eq!(
+            descending.include_end(Bound::Excluded(5)),
+            Some(Bound::Included(6))
+        );
+
+        let chars = Collator::<char>::default();
+        assert_eq!(chars.next_value(&'\u{D7FF}'), Some('\u{E000}'));
+        assert_eq!(chars.next_value(&char::MAX), None);
+    }
+}
@@ -0,0 +1,245 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef, TryCollate};
+
+/// The placement of a value which is not in the table of a [`TableCollator`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UnknownValue {
+    /// Unknown values collate before all known values
+    First,
+
+    /// Unknown values collate after all known values
+    #[default]
+    Last,
+}
+
+/// The error returned by [`StrictTableCollator::try_cmp`](TryCollate::try_cmp) when comparing
+/// a value which is not in its table.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnknownValueError;
+
+impl fmt::Display for UnknownValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("cannot collate a value which is not in the collation table")
+    }
+}
+
+impl std::error::Error for UnknownValueError {}
+
+/// A collator for a finite set of values in a custom total order defined at runtime,
+/// e.g. a user-defined ordering of categories like weekday names.
+///
+/// Values which are not in the table are placed according to an [`UnknownValue`] policy
+/// and collated relative to each other by their natural order.
+/// To reject unknown values rather than collate them, use a [`StrictTableCollator`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CollateRef, TableCollator, UnknownValue};
+///
+/// let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].map(String::from);
+/// let collator = TableCollator::new(weekdays, UnknownValue::Last);
+///
+/// let mut days = vec!["Sun", "Holiday", "Wed", "Mon"];
+/// days.sort_by(|l, r| collator.cmp_ref(*l, *r));
+/// assert_eq!(days, ["Mon", "Wed", "Sun", "Holiday"]);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(deserialize = "T: Deserialize<'de> + Eq + Hash"))
+)]
+pub struct TableCollator<T> {
+    ranks: HashMap<T, u32>,
+    unknown: UnknownValue,
+}
+
+impl<T: Eq + Hash> TableCollator<T> {
+    /// Construct a new [`TableCollator`] for the given `values`, in collation order.
+    /// If a value appears more than once, its first position is used.
+    pub fn new<I: IntoIterator<Item = T>>(values: I, unknown: UnknownValue) -> Self {
+        let mut ranks = HashMap::new();
+
+        for value in values {
+            let rank = ranks.len() as u32;
+            ranks.entry(value).or_insert(rank);
+        }
+
+        Self { ranks, unknown }
+    }
+
+    /// Construct a new [`TableCollator`] from an explicit rank for each value.
+    /// Values with equal ranks collate equal.
+    pub fn from_ranks(ranks: HashMap<T, u32>, unknown: UnknownValue) -> Self {
+        Self { ranks, unknown }
+    }
+
+    /// Return the rank of the given `value`, if it's in the table.
+    pub fn rank<Q>(&self, value: &Q) -> Option<u32>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ranks.get(value).copied()
+    }
+
+    /// The [`UnknownValue`] policy of this collator.
+    pub fn unknown(&self) -> UnknownValue {
+        self.unknown
+    }
+
+    /// Destructure this collator into its table of ranks.
+    pub fn into_ranks(self) -> HashMap<T, u32> {
+        self.ranks
+    }
+
+    fn cmp_ranked<Q>(&self, left: &Q, right: &Q) -> Ordering
+    where
+        T: Borrow<Q>,
+        Q: Ord + Hash + ?Sized,
+    {
+        match (self.rank(left), self.rank(right)) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            (None, None) => left.cmp(right),
+            (None, Some(_)) => match self.unknown {
+                UnknownValue::First => Ordering::Less,
+                UnknownValue::Last => Ordering::Greater,
+            },
+            (Some(_), None) => match self.unknown {
+                UnknownValue::First => Ordering::Greater,
+                UnknownValue::Last => Ordering::Less,
+            },
+        }
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for TableCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.unknown == other.unknown && self.ranks == other.ranks
+    }
+}
+
+impl<T: Eq + Hash> Eq for TableCollator<T> {}
+
+impl<T: Ord + Hash> Collate for TableCollator<T> {
+    type Value = T;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.cmp_ranked(left, right)
+    }
+}
+
+impl CollateRef<str> for TableCollator<String> {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        self.cmp_ranked(left, right)
+    }
+}
+
+/// A fallible collator for a finite set of values in a custom total order defined at runtime,
+/// like a [`TableCollator`], which returns an [`UnknownValueError`] when comparing a value
+/// which is not in its table.
+///
+/// This only implements [`TryCollate`], since a value outside the table is still a valid value
+/// of its type.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{StrictTableCollator, TryCollate, UnknownValueError};
+///
+/// let collator = StrictTableCollator::new(["low", "medium", "high"]);
+/// assert_eq!(collator.try_cmp(&"high", &"low"), Ok(Ordering::Greater));
+/// assert_eq!(collator.try_cmp(&"high", &"urgent"), Err(UnknownValueError));
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(bound(deserialize = "T: Deserialize<'de> + Eq + Hash"))
+)]
+pub struct StrictTableCollator<T> {
+    ranks: HashMap<T, u32>,
+}
+
+impl<T: Eq + Hash> StrictTableCollator<T> {
+    /// Construct a new [`StrictTableCollator`] for the given `values`, in collation order.
+    /// If a value appears more than once, its first position is used.
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Self::from_ranks(TableCollator::new(values, UnknownValue::default()).into_ranks())
+    }
+
+    /// Construct a new [`StrictTableCollator`] from an explicit rank for each value.
+    /// Values with equal ranks collate equal.
+    pub fn from_ranks(ranks: HashMap<T, u32>) -> Self {
+        Self { ranks }
+    }
+
+    /// Return the rank of the given `value`, if it's in the table.
+    pub fn rank<Q>(&self, value: &Q) -> Option<u32>
+    where
+        T: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ranks.get(value).copied()
+    }
+
+    /// Destructure this collator into its table of ranks.
+    pub fn into_ranks(self) -> HashMap<T, u32> {
+        self.ranks
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for StrictTableCollator<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranks == other.ranks
+    }
+}
+
+impl<T: Eq + Hash> Eq for StrictTableCollator<T> {}
+
+impl<T: Eq + Hash> TryCollate for StrictTableCollator<T> {
+    type Value = T;
+    type Error = UnknownValueError;
+
+    fn try_cmp(&self, left: &Self::Value, right: &Self::Value) -> Result<Ordering, Self::Error> {
+        match (self.rank(left), self.rank(right)) {
+            (Some(l), Some(r)) => Ok(l.cmp(&r)),
+            _ => Err(UnknownValueError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_values() {
+        let sizes = ["S", "M", "L", "XL"].map(String::from);
+
+        let first = TableCollator::new(sizes.clone(), UnknownValue::First);
+        assert_eq!(first.cmp_ref("XL", "M"), Ordering::Greater);
+        assert_eq!(first.cmp_ref("XXS", "S"), Ordering::Less);
+        assert_eq!(first.cmp_ref("XXS", "XXL"), Ordering::Greater);
+
+        let last = TableCollator::new(sizes.clone(), UnknownValue::Last);
+        assert_eq!(last.cmp_ref("XXS", "XL"), Ordering::Greater);
+        assert_eq!(last.cmp_ref("M", "M"), Ordering::Equal);
+
+        let ranks = HashMap::from([("S", 0), ("M", 1), ("medium", 1), ("L", 2)]);
+        let strict = StrictTableCollator::from_ranks(ranks);
+        assert_eq!(strict.try_cmp(&"medium", &"M"), Ok(Ordering::Equal));
+        assert_eq!(strict.try_cmp(&"L", &"XL"), Err(UnknownValueError));
+        assert_eq!(strict.rank("L"), Some(2));
+    }
+}
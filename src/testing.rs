@@ -0,0 +1,259 @@
+//! Utilities to check that a [`Collate`] implementation defines a total order.
+//!
+//! Example:
+//! ```
+//! use collate::testing::assert_collate;
+//! use collate::Collator;
+//!
+//! let samples = [3, 1, 4, 1, 5, 9, 2, 6];
+//! assert_collate(&Collator::<u32>::default(), &samples);
+//! ```
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::ControlFlow;
+
+use crate::{Collate, CollateRef};
+
+/// A violation of the laws of a total order by a collator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation<T> {
+    /// A value which does not collate equal to itself
+    Reflexivity(T),
+
+    /// A pair of values whose collation is not the reverse of their reversed collation
+    Antisymmetry(T, T),
+
+    /// Three values `a <= b <= c` for which `a <= c` does not hold
+    Transitivity(T, T, T),
+
+    /// A pair of values whose collation by reference is inconsistent with their collation
+    Inconsistent(T, T),
+
+    /// A pair of values whose collation changes when it's repeated
+    Unstable(T, T),
+}
+
+impl<T: fmt::Debug> fmt::Display for Violation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Reflexivity(a) => write!(f, "{a:?} does not collate equal to itself"),
+            Self::Antisymmetry(a, b) => {
+                write!(f, "the collation of {a:?} and {b:?} is not antisymmetric")
+            }
+            Self::Transitivity(a, b, c) => write!(
+                f,
+                "{a:?} <= {b:?} and {b:?} <= {c:?} but not {a:?} <= {c:?}"
+            ),
+            Self::Inconsistent(a, b) => write!(
+                f,
+                "the collation of {a:?} and {b:?} is inconsistent with their collation by reference"
+            ),
+            Self::Unstable(a, b) => write!(
+                f,
+                "the collation of {a:?} and {b:?} changes when it's repeated"
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Violation<T> {}
+
+/// Check that the given `collator` defines a total order over the given `samples`,
+/// i.e. that its collation is reflexive, antisymmetric, and transitive.
+///
+/// Transitivity is checked over every triple of samples, so the number of samples
+/// should be kept small.
+pub fn check_collate<'a, C: Collate>(
+    collator: &C,
+    samples: &'a [C::Value],
+) -> Result<(), Violation<&'a C::Value>> {
+    match for_each_violation(collator, samples, false, ControlFlow::Break) {
+        ControlFlow::Break(violation) => Err(violation),
+        ControlFlow::Continue(()) => Ok(()),
+    }
+}
+
+/// Check that the given `collator` defines a total order over the given `samples`,
+/// like [`check_collate`], and return a report of every violation found rather than
+/// only the first. This also checks that the collation of each pair of samples is stable,
+/// i.e. that it does not change when it's repeated.
+///
+/// A report which is empty means that no violation was found.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::testing::{check_total_order, Violation};
+/// use collate::Collate;
+///
+/// // a collator which does not find any value equal to itself
+/// #[derive(Eq, PartialEq)]
+/// struct Strict;
+///
+/// impl Collate for Strict {
+///     type Value = u8;
+///
+///     fn cmp(&self, left: &u8, right: &u8) -> Ordering {
+///         if left < right { Ordering::Less } else { Ordering::Greater }
+///     }
+/// }
+///
+/// let report = check_total_order(&Strict, &[1, 2]);
+/// assert!(report.contains(&Violation::Reflexivity(&1)));
+/// assert!(report.contains(&Violation::Reflexivity(&2)));
+/// ```
+pub fn check_total_order<'a, C: Collate>(
+    collator: &C,
+    samples: &'a [C::Value],
+) -> Vec<Violation<&'a C::Value>> {
+    let mut report = Vec::new();
+
+    let _ = for_each_violation(collator, samples, true, |violation| {
+        report.push(violation);
+        ControlFlow::<()>::Continue(())
+    });
+
+    report
+}
+
+// call `on_violation` with each violation of a total order by `collator` over `samples`
+// (including unstable collations, if `check_stable` is set) until it returns `Break`
+fn for_each_violation<'a, C, F, B>(
+    collator: &C,
+    samples: &'a [C::Value],
+    check_stable: bool,
+    mut on_violation: F,
+) -> ControlFlow<B>
+where
+    C: Collate,
+    F: FnMut(Violation<&'a C::Value>) -> ControlFlow<B>,
+{
+    for a in samples {
+        if collator.cmp(a, a) != Ordering::Equal {
+            on_violation(Violation::Reflexivity(a))?;
+        }
+    }
+
+    for a in samples {
+        for b in samples {
+            let ab = collator.cmp(a, b);
+
+            if check_stable && collator.cmp(a, b) != ab {
+                on_violation(Violation::Unstable(a, b))?;
+            } else if ab != collator.cmp(b, a).reverse() {
+                on_violation(Violation::Antisymmetry(a, b))?;
+            }
+        }
+    }
+
+    for a in samples {
+        for b in samples {
+            let ab = collator.cmp(a, b);
+            if ab == Ordering::Greater {
+                continue;
+            }
+
+            for c in samples {
+                let bc = collator.cmp(b, c);
+                if bc == Ordering::Greater {
+                    continue;
+                }
+
+                // if either step is strict then so is the result, otherwise all three are equal
+                let expected = ab.then(bc);
+
+                if collator.cmp(a, c) != expected {
+                    on_violation(Violation::Transitivity(a, b, c))?;
+                }
+            }
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Check that the collation of every pair of the given `samples` by reference to `Q`
+/// is consistent with their collation by value.
+pub fn check_collate_ref<'a, C, Q>(
+    collator: &C,
+    samples: &'a [C::Value],
+) -> Result<(), Violation<&'a C::Value>>
+where
+    C: CollateRef<Q>,
+    C::Value: Borrow<Q>,
+    Q: ?Sized,
+{
+    for a in samples {
+        for b in samples {
+            if collator.cmp(a, b) != collator.cmp_ref(a.borrow(), b.borrow()) {
+                return Err(Violation::Inconsistent(a, b));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Assert that the given `collator` defines a total order over the given `samples`.
+///
+/// Panics with a description of the first violation found, if any.
+/// See [`check_collate`] for details.
+pub fn assert_collate<C>(collator: &C, samples: &[C::Value])
+where
+    C: Collate,
+    C::Value: fmt::Debug,
+{
+    if let Err(violation) = check_collate(collator, samples) {
+        panic!("{violation}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+
+    /// A collator which compares integers modulo 3, which is not transitive
+    #[derive(Eq, PartialEq)]
+    struct Rock;
+
+    impl Collate for Rock {
+        type Value = u8;
+
+        fn cmp(&self, left: &u8, right: &u8) -> Ordering {
+            match (3 + left - right) % 3 {
+                0 => Ordering::Equal,
+                1 => Ordering::Greater,
+                _ => Ordering::Less,
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_collate() {
+        let samples = [0u8, 1, 2];
+
+        assert_eq!(check_collate(&Collator::default(), &samples), Ok(()));
+        assert_eq!(
+            check_collate_ref::<_, u8>(&Collator::default(), &samples),
+            Ok(())
+        );
+
+        assert_eq!(
+            check_collate(&Rock, &samples),
+            Err(Violation::Transitivity(&0, &1, &2))
+        );
+
+        assert!(check_total_order(&Collator::default(), &samples).is_empty());
+        assert_eq!(
+            check_total_order(&Rock, &samples),
+            [
+                Violation::Transitivity(&0, &1, &2),
+                Violation::Transitivity(&1, &2, &0),
+                Violation::Transitivity(&2, &0, &1),
+            ]
+        );
+    }
+}
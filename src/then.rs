@@ -0,0 +1,69 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Collate;
+
+/// A collator which collates values using its `first` collator, falling back to its `second`
+/// collator to break ties. Construct a [`Then`] collator using [`Collate::then`].
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, Collator};
+///
+/// #[derive(Eq, PartialEq)]
+/// struct ByLength;
+///
+/// impl Collate for ByLength {
+///     type Value = String;
+///
+///     fn cmp(&self, left: &String, right: &String) -> Ordering {
+///         left.len().cmp(&right.len())
+///     }
+/// }
+///
+/// let collator = ByLength.then(Collator::<String>::default());
+///
+/// let mut values = ["bb", "c", "aa", "a"].map(String::from);
+/// values.sort_by(|l, r| collator.cmp(l, r));
+/// assert_eq!(values, ["a", "c", "aa", "bb"]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Then<C1, C2> {
+    first: C1,
+    second: C2,
+}
+
+impl<C1, C2> Then<C1, C2> {
+    /// Construct a new [`Then`] collator.
+    pub fn new(first: C1, second: C2) -> Self {
+        Self { first, second }
+    }
+
+    /// Borrow the primary collator.
+    pub fn first(&self) -> &C1 {
+        &self.first
+    }
+
+    /// Borrow the collator used to break ties.
+    pub fn second(&self) -> &C2 {
+        &self.second
+    }
+}
+
+impl<C1, C2> Collate for Then<C1, C2>
+where
+    C1: Collate,
+    C2: Collate<Value = C1::Value>,
+{
+    type Value = C1::Value;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        self.first
+            .cmp(left, right)
+            .then_with(|| self.second.cmp(left, right))
+    }
+}
@@ -0,0 +1,102 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateKey};
+
+/// A collator for tuples, which collates each field of a tuple using the collator
+/// at the same position in a tuple of collators, in order.
+///
+/// [`Collate`] is implemented for tuples of up to 12 collators.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{Collate, Collator, FloatCollator, TupleCollator};
+///
+/// let collator = TupleCollator::new((Collator::<String>::default(), FloatCollator::<f64>::default()));
+///
+/// let left = ("a".to_string(), 2.5);
+/// let right = ("a".to_string(), f64::NAN);
+/// assert_eq!(collator.cmp(&left, &right), Ordering::Less);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TupleCollator<C> {
+    collators: C,
+}
+
+impl<C> TupleCollator<C> {
+    /// Construct a new [`TupleCollator`] from a tuple of `collators`.
+    pub fn new(collators: C) -> Self {
+        Self { collators }
+    }
+
+    /// Borrow the tuple of collators.
+    pub fn inner(&self) -> &C {
+        &self.collators
+    }
+}
+
+macro_rules! tuple_collator {
+    ($($collator:ident $i:tt),+) => {
+        impl<$($collator: Collate),+> Collate for TupleCollator<($($collator,)+)> {
+            type Value = ($($collator::Value,)+);
+
+            fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+                $(
+                    match self.collators.$i.cmp(&left.$i, &right.$i) {
+                        Ordering::Equal => {}
+                        order => return order,
+                    }
+                )+
+
+                Ordering::Equal
+            }
+        }
+
+        impl<$($collator: CollateKey),+> CollateKey for TupleCollator<($($collator,)+)> {
+            fn write_key(&self, value: &Self::Value, key: &mut Vec<u8>) {
+                // each key is self-delimiting, so the keys of the fields can be concatenated
+                $(self.collators.$i.write_key(&value.$i, key);)+
+            }
+        }
+    };
+}
+
+tuple_collator!(C0 0);
+tuple_collator!(C0 0, C1 1);
+tuple_collator!(C0 0, C1 1, C2 2);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6, C7 7);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6, C7 7, C8 8);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6, C7 7, C8 8, C9 9);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6, C7 7, C8 8, C9 9, C10 10);
+tuple_collator!(C0 0, C1 1, C2 2, C3 3, C4 4, C5 5, C6 6, C7 7, C8 8, C9 9, C10 10, C11 11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Collator, DirectedCollator, Direction};
+
+    #[test]
+    fn test_tuple_collator() {
+        let collator = TupleCollator::new((
+            Collator::<u32>::default(),
+            DirectedCollator::new(Collator::<u32>::default(), Direction::Descending),
+            Collator::<bool>::default(),
+        ));
+
+        let mut tuples = vec![(1, 1, true), (0, 1, false), (1, 2, false), (1, 1, false)];
+        tuples.sort_by(|l, r| collator.cmp(l, r));
+
+        assert_eq!(
+            tuples,
+            vec![(0, 1, false), (1, 2, false), (1, 1, false), (1, 1, true)]
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+
+use semver::Version;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Collate, CollateRef};
+
+/// A collator for semantic versions, according to the precedence rules of
+/// [SemVer 2.0.0](https://semver.org/#spec-item-11).
+///
+/// A pre-release version collates before the associated normal version, and pre-release
+/// identifiers are compared field by field, numerically if both are numeric.
+/// Build metadata is ignored, so `1.0.0+a` and `1.0.0+b` collate equal.
+///
+/// Version strings can be collated directly using [`CollateRef<str>`]. A string which is
+/// not a valid version collates after all valid versions, and by code point relative to other
+/// invalid strings.
+///
+/// Example:
+/// ```
+/// use std::cmp::Ordering;
+/// use collate::{CollateRef, VersionCollator};
+///
+/// let mut versions = ["1.0.0", "1.0.0-beta.11", "1.0.0-beta.2", "1.0.0-alpha", "0.9.12"];
+/// versions.sort_by(|l, r| VersionCollator.cmp_ref(*l, *r));
+/// assert_eq!(versions, ["0.9.12", "1.0.0-alpha", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0"]);
+///
+/// assert_eq!(VersionCollator.cmp_ref("1.0.0+linux", "1.0.0+macos"), Ordering::Equal);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VersionCollator;
+
+impl Collate for VersionCollator {
+    type Value = Version;
+
+    fn cmp(&self, left: &Self::Value, right: &Self::Value) -> Ordering {
+        left.major
+            .cmp(&right.major)
+            .then(left.minor.cmp(&right.minor))
+            .then(left.patch.cmp(&right.patch))
+            .then_with(|| left.pre.cmp(&right.pre))
+    }
+}
+
+impl CollateRef<str> for VersionCollator {
+    fn cmp_ref(&self, left: &str, right: &str) -> Ordering {
+        match (Version::parse(left), Version::parse(right)) {
+            (Ok(l), Ok(r)) => self.cmp(&l, &r),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => left.cmp(right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_collator() {
+        // the example from the SemVer specification
+        let expected = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+
+        for pair in expected.windows(2) {
+            assert_eq!(VersionCollator.cmp_ref(pair[0], pair[1]), Ordering::Less);
+        }
+
+        assert_eq!(
+            VersionCollator.cmp_ref("10.0.0", "9.0.0"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            VersionCollator.cmp_ref("latest", "99.0.0"),
+            Ordering::Greater
+        );
+        assert_eq!(VersionCollator.cmp_ref("latest", "nightly"), Ordering::Less);
+    }
+}
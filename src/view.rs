@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+use std::ops::RangeBounds;
+
+use crate::bisect::bisect_range_by;
+use crate::{CollateEquivalent, CollateRef};
+
+/// An entry in the delta of a [`MergedView`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Delta<T> {
+    /// Insert the given value, replacing any collation-equal value in the base
+    Put(T),
+
+    /// Delete any collation-equal value from the base
+    Delete(T),
+}
+
+impl<T> Delta<T> {
+    /// Borrow the value (or tombstone key) of this [`Delta`].
+    pub fn value(&self) -> &T {
+        match self {
+            Self::Put(value) => value,
+            Self::Delete(key) => key,
+        }
+    }
+}
+
+/// A read-only view of a collated `base` overlaid with a collated `delta`.
+///
+/// Both the `base` and the `delta` **must** be collated, and the `delta` must not contain
+/// two entries which are equal according to the collator. The `base` may contain several values
+/// which are equal according to the collator; a delta entry replaces or deletes all of them.
+/// If either is not collated, the order of the merged view is undefined.
+///
+/// Example:
+/// ```
+/// use collate::{Collator, Delta, MergedView};
+///
+/// let base = [1, 2, 3, 5];
+/// let delta = [Delta::Delete(2), Delta::Put(4), Delta::Delete(6)];
+/// let view = MergedView::new(Collator::default(), &base, &delta);
+///
+/// assert_eq!(view.iter().copied().collect::<Vec<_>>(), [1, 3, 4, 5]);
+/// assert_eq!(view.range(2..5).copied().collect::<Vec<_>>(), [3, 4]);
+/// assert_eq!(view.get(&2), None);
+/// assert_eq!(view.get(&4), Some(&4));
+/// ```
+#[derive(Clone)]
+pub struct MergedView<'a, C, T> {
+    collator: C,
+    base: &'a [T],
+    delta: &'a [Delta<T>],
+}
+
+impl<'a, C, T> MergedView<'a, C, T>
+where
+    C: CollateRef<T>,
+{
+    /// Construct a new [`MergedView`] of the given `base` overlaid with the given `delta`.
+    pub fn new(collator: C, base: &'a [T], delta: &'a [Delta<T>]) -> Self {
+        Self {
+            collator,
+            base,
+            delta,
+        }
+    }
+
+    /// Borrow the collator used by this view.
+    pub fn collator(&self) -> &C {
+        &self.collator
+    }
+
+    /// Look up the value in this view which is equal to the given `key`, if any.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a T>
+    where
+        C: CollateEquivalent<T, Q>,
+        Q: ?Sized,
+    {
+        let delta = self
+            .delta
+            .binary_search_by(|entry| self.collator.cmp_equivalent(entry.value(), key));
+
+        if let Ok(i) = delta {
+            return match &self.delta[i] {
+                Delta::Put(value) => Some(value),
+                Delta::Delete(_) => None,
+            };
+        }
+
+        self.base
+            .binary_search_by(|value| self.collator.cmp_equivalent(value, key))
+            .ok()
+            .map(|i| &self.base[i])
+    }
+
+    /// Iterate over all the values in this view, in collation order.
+    pub fn iter(&self) -> ViewIter<'_, 'a, C, T> {
+        ViewIter {
+            collator: &self.collator,
+            base: self.base,
+            delta: self.delta,
+        }
+    }
+
+    /// Iterate over the values in this view which lie within the given `range`,
+    /// in collation order.
+    pub fn range<Q, R>(&self, range: R) -> ViewIter<'_, 'a, C, T>
+    where
+        C: CollateEquivalent<T, Q>,
+        Q: ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let collator = &self.collator;
+
+        let (start, end) = bisect_range_by(self.base, &range, collator, |value| value);
+        let base = &self.base[start..end];
+
+        let (start, end) = bisect_range_by(self.delta, &range, collator, Delta::value);
+        let delta = &self.delta[start..end];
+
+        ViewIter {
+            collator,
+            base,
+            delta,
+        }
+    }
+}
+
+/// An iterator over the values in a [`MergedView`].
+pub struct ViewIter<'c, 'a, C, T> {
+    collator: &'c C,
+    base: &'a [T],
+    delta: &'a [Delta<T>],
+}
+
+impl<'c, 'a, C, T> Iterator for ViewIter<'c, 'a, C, T>
+where
+    C: CollateRef<T>,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let order = match (self.base.first(), self.delta.first()) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(value), Some(entry)) => self.collator.cmp_ref(value, entry.value()),
+            };
+
+            if order == Ordering::Less {
+                let (value, base) = self.base.split_first().expect("base value");
+                self.base = base;
+                return Some(value);
+            }
+
+            let (entry, delta) = self.delta.split_first().expect("delta entry");
+
+            if order == Ordering::Equal {
+                // these values are overwritten or deleted by the delta
+                let equal = self.base.partition_point(|value| {
+                    self.collator.cmp_ref(value, entry.value()) == Ordering::Equal
+                });
+
+                self.base = &self.base[equal..];
+            }
+
+            self.delta = delta;
+
+            if let Delta::Put(value) = entry {
+                return Some(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Collator;
+    use std::ops::Bound;
+
+    #[test]
+    fn test_merged_view_range() {
+        let collator = Collator::<u32>::default();
+
+        let base = [1, 3, 5, 7, 9];
+        let delta = [
+            Delta::Put(0),
+            Delta::Delete(3),
+            Delta::Put(4),
+            Delta::Put(7),
+            Delta::Delete(9),
+            Delta::Put(10),
+        ];
+
+        let view = MergedView::new(collator, &base, &delta);
+
+        let all = view.iter().copied().collect::<Vec<u32>>();
+        assert_eq!(all, vec![0, 1, 4, 5, 7, 10]);
+
+        let range = view.range(3..=7).copied().collect::<Vec<u32>>();
+        assert_eq!(range, vec![4, 5, 7]);
+
+        let range = view.range((Bound::Excluded(5), Bound::Unbounded));
+        assert_eq!(range.copied().collect::<Vec<u32>>(), vec![7, 10]);
+
+        assert_eq!(view.range(6..7).count(), 0);
+    }
+
+    #[test]
+    fn test_merged_view_duplicates() {
+        let collator = Collator::<u32>::default();
+
+        let base = [1, 2, 2, 3, 3];
+        let delta = [Delta::Delete(2), Delta::Put(3)];
+        let view = MergedView::new(collator, &base, &delta);
+
+        assert_eq!(view.get(&2), None);
+        assert_eq!(view.iter().copied().collect::<Vec<u32>>(), vec![1, 3]);
+    }
+}